@@ -0,0 +1,332 @@
+use crate::github::{DiffStat, EventItem, EventKind, ReviewState, host_dir_name};
+use crate::range::DateRange;
+use anyhow::Context;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{OptionalExtension, params};
+use std::path::{Path, PathBuf};
+
+const SCHEMA_VERSION: i32 = 1;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS events (
+    login TEXT NOT NULL COLLATE NOCASE,
+    kind TEXT NOT NULL,
+    url TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    repository TEXT NOT NULL,
+    subject_title TEXT NOT NULL,
+    subject_url TEXT NOT NULL,
+    body TEXT,
+    additions INTEGER,
+    deletions INTEGER,
+    review_state TEXT,
+    synced_at TEXT NOT NULL,
+    PRIMARY KEY (login, url, kind, created_at)
+);
+CREATE INDEX IF NOT EXISTS events_by_time ON events (login, created_at);
+";
+
+/// Events synced from one host, kept in a local SQLite database.
+///
+/// Events are identified by their URL. Opened, closed and reopened events all link to the
+/// issue or pull request itself, so the kind and time are part of the key as well; syncing
+/// the same event again updates it in place.
+pub struct Archive {
+    connection: rusqlite::Connection,
+}
+
+impl Archive {
+    /// `$XDG_DATA_HOME/gh-furik`, falling back to `~/.local/share/gh-furik`.
+    pub fn default_dir() -> Option<PathBuf> {
+        crate::xdg::app_dir("XDG_DATA_HOME", ".local/share")
+    }
+
+    /// Database file for `host` under `dir`.
+    pub fn path(dir: &Path, host: &str) -> PathBuf {
+        dir.join(format!("{}.sqlite3", host_dir_name(host)))
+    }
+
+    /// Opens the archive at `path`, creating it when `create` is set.
+    pub fn open(path: &Path, create: bool) -> anyhow::Result<Self> {
+        if create {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir).with_context(|| {
+                    format!("failed to create archive directory {}", dir.display())
+                })?;
+            }
+        } else if !path.exists() {
+            anyhow::bail!(
+                "no archive at {}; run `gh furik sync` first",
+                path.display()
+            );
+        }
+        let connection = rusqlite::Connection::open(path)
+            .with_context(|| format!("failed to open archive {}", path.display()))?;
+        Self::init(connection).with_context(|| format!("failed to open archive {}", path.display()))
+    }
+
+    #[cfg(test)]
+    fn open_in_memory() -> Self {
+        Self::init(rusqlite::Connection::open_in_memory().unwrap()).unwrap()
+    }
+
+    fn init(connection: rusqlite::Connection) -> anyhow::Result<Self> {
+        let version: i32 = connection.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        if version > SCHEMA_VERSION {
+            anyhow::bail!("archive was written by a newer version of gh-furik");
+        }
+        connection.execute_batch(SCHEMA)?;
+        connection.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+        Ok(Self { connection })
+    }
+
+    /// Stores `login`'s events and returns how many were not archived before.
+    pub fn store(&mut self, login: &str, items: &[EventItem]) -> anyhow::Result<usize> {
+        let synced_at = timestamp(Utc::now());
+        let transaction = self.connection.transaction()?;
+        let mut added = 0;
+        {
+            let mut exists = transaction.prepare(
+                "SELECT 1 FROM events WHERE login = ?1 AND url = ?2 AND kind = ?3 AND created_at = ?4",
+            )?;
+            let mut upsert = transaction.prepare(
+                "INSERT INTO events (login, kind, url, created_at, repository, subject_title,
+                     subject_url, body, additions, deletions, review_state, synced_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+                 ON CONFLICT (login, url, kind, created_at) DO UPDATE SET
+                     repository = excluded.repository,
+                     subject_title = excluded.subject_title,
+                     subject_url = excluded.subject_url,
+                     body = excluded.body,
+                     additions = excluded.additions,
+                     deletions = excluded.deletions,
+                     review_state = excluded.review_state,
+                     synced_at = excluded.synced_at",
+            )?;
+            for item in items {
+                let kind = item.kind.name();
+                let created_at = timestamp(item.created_at);
+                let known = exists
+                    .query_row(params![login, item.url, kind, created_at], |_| Ok(()))
+                    .optional()?
+                    .is_some();
+                upsert.execute(params![
+                    login,
+                    kind,
+                    item.url,
+                    created_at,
+                    item.repository,
+                    item.subject_title,
+                    item.subject_url,
+                    item.body,
+                    item.diff_stat.map(|stat| stat.additions),
+                    item.diff_stat.map(|stat| stat.deletions),
+                    item.review_state.as_ref().map(ReviewState::name),
+                    synced_at,
+                ])?;
+                if !known {
+                    added += 1;
+                }
+            }
+        }
+        transaction.commit()?;
+        Ok(added)
+    }
+
+    /// Logins with archived events.
+    pub fn logins(&self) -> anyhow::Result<Vec<String>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT DISTINCT login FROM events ORDER BY login")?;
+        let logins = statement
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<_, _>>()?;
+        Ok(logins)
+    }
+
+    /// `login`'s archived events within `range`.
+    pub fn events(&self, login: &str, range: DateRange) -> anyhow::Result<Vec<EventItem>> {
+        let mut statement = self.connection.prepare(
+            "SELECT kind, created_at, url, body, repository, subject_title, subject_url,
+                 additions, deletions, review_state
+             FROM events
+             WHERE login = ?1 AND created_at >= ?2 AND created_at <= ?3
+             ORDER BY created_at, url",
+        )?;
+        let rows = statement.query_map(
+            params![login, timestamp(range.start()), timestamp(range.end())],
+            |row| {
+                Ok(ArchivedEvent {
+                    kind: row.get(0)?,
+                    created_at: row.get(1)?,
+                    url: row.get(2)?,
+                    body: row.get(3)?,
+                    repository: row.get(4)?,
+                    subject_title: row.get(5)?,
+                    subject_url: row.get(6)?,
+                    additions: row.get(7)?,
+                    deletions: row.get(8)?,
+                    review_state: row.get(9)?,
+                })
+            },
+        )?;
+        rows.map(|row| row?.into_event_item()).collect()
+    }
+}
+
+/// Times are stored in UTC with a fixed width, so that they sort as text.
+fn timestamp(dt: DateTime<Utc>) -> String {
+    dt.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+struct ArchivedEvent {
+    kind: String,
+    created_at: String,
+    url: String,
+    body: Option<String>,
+    repository: String,
+    subject_title: String,
+    subject_url: String,
+    additions: Option<u64>,
+    deletions: Option<u64>,
+    review_state: Option<String>,
+}
+
+impl ArchivedEvent {
+    fn into_event_item(self) -> anyhow::Result<EventItem> {
+        let kind = EventKind::ALL
+            .into_iter()
+            .find(|kind| kind.name() == self.kind)
+            .with_context(|| format!("unknown event kind `{}` in archive", self.kind))?;
+        let review_state = match &self.review_state {
+            Some(name) => Some(
+                ReviewState::ALL
+                    .into_iter()
+                    .find(|state| state.name() == name)
+                    .with_context(|| format!("unknown review state `{name}` in archive"))?,
+            ),
+            None => None,
+        };
+        let created_at = DateTime::parse_from_rfc3339(&self.created_at)
+            .with_context(|| format!("invalid event time `{}` in archive", self.created_at))?
+            .with_timezone(&Utc);
+        Ok(EventItem {
+            kind,
+            created_at,
+            url: self.url,
+            body: self.body,
+            repository: self.repository,
+            subject_title: self.subject_title,
+            subject_url: self.subject_url,
+            diff_stat: self
+                .additions
+                .zip(self.deletions)
+                .map(|(additions, deletions)| DiffStat {
+                    additions,
+                    deletions,
+                }),
+            review_state,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::range::Zone;
+    use chrono::TimeZone;
+
+    fn item(kind: EventKind, url: &str, day: u32) -> EventItem {
+        EventItem {
+            kind,
+            created_at: Utc.with_ymd_and_hms(2025, 1, day, 12, 0, 0).unwrap(),
+            url: url.to_string(),
+            body: None,
+            repository: "o/r".to_string(),
+            subject_title: "Fix".to_string(),
+            subject_url: "https://example.test/pull/1".to_string(),
+            diff_stat: None,
+            review_state: None,
+        }
+    }
+
+    fn january(from: u32, to: u32) -> DateRange {
+        DateRange {
+            from: chrono::NaiveDate::from_ymd_opt(2025, 1, from).unwrap(),
+            to: chrono::NaiveDate::from_ymd_opt(2025, 1, to).unwrap(),
+            zone: Zone::UTC,
+        }
+    }
+
+    #[test]
+    fn store_deduplicates_events_and_keeps_kinds_sharing_a_url() {
+        let mut archive = Archive::open_in_memory();
+        let opened = item(
+            EventKind::PullRequestOpened,
+            "https://example.test/pull/1",
+            2,
+        );
+        let merged = item(
+            EventKind::PullRequestMerged,
+            "https://example.test/pull/1",
+            5,
+        );
+
+        assert_eq!(
+            archive
+                .store("alice", std::slice::from_ref(&opened))
+                .unwrap(),
+            1
+        );
+        let edited = EventItem {
+            subject_title: "Fix the bug".to_string(),
+            ..opened
+        };
+        assert_eq!(archive.store("Alice", &[edited, merged]).unwrap(), 1);
+
+        let events = archive.events("alice", january(1, 31)).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].subject_title, "Fix the bug");
+        assert_eq!(events[1].kind, EventKind::PullRequestMerged);
+        assert_eq!(archive.logins().unwrap(), ["alice"]);
+    }
+
+    #[test]
+    fn events_round_trip_within_the_range() {
+        let mut archive = Archive::open_in_memory();
+        let review = EventItem {
+            review_state: Some(ReviewState::Approved),
+            body: Some("LGTM".to_string()),
+            ..item(
+                EventKind::PullRequestReview,
+                "https://example.test/pull/1#r1",
+                3,
+            )
+        };
+        let commit = EventItem {
+            diff_stat: Some(DiffStat {
+                additions: 10,
+                deletions: 2,
+            }),
+            ..item(EventKind::Commit, "https://example.test/commit/abc", 20)
+        };
+        archive
+            .store("alice", &[review.clone(), commit.clone()])
+            .unwrap();
+
+        assert_eq!(
+            archive.events("alice", january(1, 31)).unwrap(),
+            [review, commit]
+        );
+        assert_eq!(archive.events("alice", january(4, 19)).unwrap(), []);
+        assert_eq!(archive.events("bob", january(1, 31)).unwrap(), []);
+    }
+
+    #[test]
+    fn path_is_per_host() {
+        assert_eq!(
+            Archive::path(Path::new("/data"), "GHE.example.com:8443"),
+            PathBuf::from("/data/ghe.example.com_8443.sqlite3")
+        );
+    }
+}
@@ -0,0 +1,296 @@
+use crate::github::EventKind;
+
+/// Repository filter built from `--repo`, `--exclude-repo` and `--org`.
+///
+/// Patterns match `owner/name` case-insensitively; `*` matches any run of characters
+/// and `?` a single character.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepoFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+    orgs: Vec<String>,
+}
+
+impl RepoFilter {
+    /// Builds a filter; `--repo` patterns starting with `!` are treated as exclusions.
+    pub fn new(repos: Vec<String>, exclude_repos: Vec<String>, orgs: Vec<String>) -> Self {
+        let mut include = Vec::new();
+        let mut exclude = exclude_repos;
+        for pattern in repos {
+            match pattern.strip_prefix('!') {
+                Some(negated) => exclude.push(negated.to_string()),
+                None => include.push(pattern),
+            }
+        }
+        Self {
+            include,
+            exclude,
+            orgs,
+        }
+    }
+
+    pub fn matches(&self, repository: &str) -> bool {
+        let included = (self.include.is_empty() && self.orgs.is_empty())
+            || self
+                .include
+                .iter()
+                .any(|pattern| glob_match(pattern, repository))
+            || repository.split_once('/').is_some_and(|(owner, _)| {
+                self.orgs.iter().any(|org| org.eq_ignore_ascii_case(owner))
+            });
+        included
+            && !self
+                .exclude
+                .iter()
+                .any(|pattern| glob_match(pattern, repository))
+    }
+
+    /// Search qualifiers equivalent to this filter, as far as GitHub search can express it.
+    ///
+    /// Inclusions are only pushed down when every one of them is a literal repository or
+    /// an organization, since glob patterns cannot be expressed as qualifiers. Events are
+    /// still filtered with [`RepoFilter::matches`] afterwards.
+    pub fn search_qualifiers(&self) -> String {
+        let mut qualifiers = Vec::new();
+        if self.include.iter().all(|pattern| !is_glob(pattern)) {
+            qualifiers.extend(self.include.iter().map(|repo| format!("repo:{repo}")));
+            qualifiers.extend(self.orgs.iter().map(|org| format!("org:{org}")));
+        }
+        qualifiers.extend(
+            self.exclude
+                .iter()
+                .filter(|pattern| !is_glob(pattern))
+                .map(|repo| format!("-repo:{repo}")),
+        );
+        qualifiers.join(" ")
+    }
+}
+
+/// Event kind filter built from `--kind` and `--exclude-kind`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KindFilter {
+    include: Vec<EventKind>,
+    exclude: Vec<EventKind>,
+}
+
+impl KindFilter {
+    /// Builds a filter from names already validated by [`parse_kind`].
+    pub fn new(kinds: &[String], exclude_kinds: &[String]) -> Self {
+        let expand = |names: &[String]| -> Vec<EventKind> {
+            names.iter().flat_map(|name| kinds_named(name)).collect()
+        };
+        Self {
+            include: expand(kinds),
+            exclude: expand(exclude_kinds),
+        }
+    }
+
+    pub fn matches(&self, kind: &EventKind) -> bool {
+        (self.include.is_empty() || self.include.contains(kind)) && !self.exclude.contains(kind)
+    }
+
+    /// Whether any of `kinds` can pass the filter, i.e. whether a query producing them is needed.
+    pub fn wants_any(&self, kinds: &[EventKind]) -> bool {
+        kinds.iter().any(|kind| self.matches(kind))
+    }
+
+    /// Whether `--kind` explicitly names any of `kinds` that is not excluded.
+    pub fn names_any(&self, kinds: &[EventKind]) -> bool {
+        kinds
+            .iter()
+            .any(|kind| self.include.contains(kind) && !self.exclude.contains(kind))
+    }
+}
+
+/// Accepts an exact kind name such as `pull_request_merged` or a group such as `merged`.
+pub fn parse_kind(input: &str) -> Result<String, String> {
+    let name = input.trim().to_ascii_lowercase().replace('-', "_");
+    if kinds_named(&name).is_empty() {
+        return Err(format!(
+            "unknown event kind `{input}`; expected comment, review, opened, closed, reopened, \
+             merged, commit, answer or a kind name such as pull_request_merged"
+        ));
+    }
+    Ok(name)
+}
+
+fn kinds_named(name: &str) -> Vec<EventKind> {
+    EventKind::ALL
+        .into_iter()
+        .filter(|kind| kind.name() == name || kind_group(kind) == name)
+        .collect()
+}
+
+fn kind_group(kind: &EventKind) -> &'static str {
+    match kind {
+        EventKind::IssueComment
+        | EventKind::PullRequestReviewComment
+        | EventKind::DiscussionComment => "comment",
+        EventKind::PullRequestReview => "review",
+        EventKind::IssueOpened | EventKind::PullRequestOpened | EventKind::DiscussionOpened => {
+            "opened"
+        }
+        EventKind::IssueClosed | EventKind::PullRequestClosed => "closed",
+        EventKind::PullRequestMerged => "merged",
+        EventKind::IssueReopened | EventKind::PullRequestReopened => "reopened",
+        EventKind::Commit => "commit",
+        EventKind::DiscussionAnswerMarked => "answer",
+    }
+}
+
+fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?'])
+}
+
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn kinds(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| parse_kind(name).unwrap()).collect()
+    }
+
+    #[test]
+    fn kind_groups_expand_to_event_kinds() {
+        let filter = KindFilter::new(&kinds(&["comment", "Merged"]), &[]);
+
+        assert!(filter.matches(&EventKind::IssueComment));
+        assert!(filter.matches(&EventKind::PullRequestReviewComment));
+        assert!(filter.matches(&EventKind::PullRequestMerged));
+        assert!(!filter.matches(&EventKind::PullRequestClosed));
+        assert!(!filter.wants_any(&[EventKind::IssueOpened, EventKind::IssueClosed]));
+    }
+
+    #[test]
+    fn names_any_requires_an_explicit_kind() {
+        let reopened = [EventKind::IssueReopened, EventKind::PullRequestReopened];
+
+        assert!(!KindFilter::default().names_any(&reopened));
+        assert!(KindFilter::new(&kinds(&["reopened"]), &[]).names_any(&reopened));
+        assert!(
+            !KindFilter::new(&kinds(&["reopened"]), &kinds(&["reopened"])).names_any(&reopened)
+        );
+    }
+
+    #[test]
+    fn exclude_kind_accepts_exact_names() {
+        let filter = KindFilter::new(&[], &kinds(&["pull-request-review-comment"]));
+
+        assert!(filter.matches(&EventKind::PullRequestReview));
+        assert!(!filter.matches(&EventKind::PullRequestReviewComment));
+        assert!(filter.wants_any(&[
+            EventKind::PullRequestReview,
+            EventKind::PullRequestReviewComment
+        ]));
+    }
+
+    #[test]
+    fn parse_kind_rejects_unknown_names() {
+        assert!(
+            parse_kind("pushed")
+                .unwrap_err()
+                .contains("unknown event kind")
+        );
+    }
+
+    #[test]
+    fn glob_match_supports_wildcards() {
+        assert!(glob_match("myorg/*", "myorg/app"));
+        assert!(glob_match("myorg/sandbox-*", "MyOrg/Sandbox-1"));
+        assert!(glob_match("*/dotfiles", "alice/dotfiles"));
+        assert!(glob_match("o/r?", "o/r1"));
+        assert!(!glob_match("o/r?", "o/r"));
+        assert!(!glob_match("myorg/*", "other/app"));
+        assert!(glob_match("a*b*c", "axxbyyc"));
+        assert!(!glob_match("a*b*c", "axxbyy"));
+    }
+
+    #[test]
+    fn empty_filter_matches_everything() {
+        assert!(RepoFilter::default().matches("o/r"));
+    }
+
+    #[test]
+    fn repo_patterns_with_negation() {
+        let filter = RepoFilter::new(strings(&["myorg/*", "!myorg/sandbox-*"]), vec![], vec![]);
+
+        assert!(filter.matches("myorg/app"));
+        assert!(!filter.matches("myorg/sandbox-1"));
+        assert!(!filter.matches("alice/dotfiles"));
+    }
+
+    #[test]
+    fn org_and_exclude_repo() {
+        let filter = RepoFilter::new(vec![], strings(&["work/legacy"]), strings(&["Work"]));
+
+        assert!(filter.matches("work/app"));
+        assert!(!filter.matches("work/legacy"));
+        assert!(!filter.matches("alice/dotfiles"));
+    }
+
+    #[test]
+    fn exclusions_alone_keep_other_repositories() {
+        let filter = RepoFilter::new(vec![], strings(&["alice/*"]), vec![]);
+
+        assert!(filter.matches("work/app"));
+        assert!(!filter.matches("alice/dotfiles"));
+    }
+
+    #[test]
+    fn search_qualifiers_push_literal_patterns() {
+        let filter = RepoFilter::new(
+            strings(&["work/app", "!work/app-legacy"]),
+            strings(&["work/tmp-*"]),
+            strings(&["oss"]),
+        );
+
+        assert_eq!(
+            filter.search_qualifiers(),
+            "repo:work/app org:oss -repo:work/app-legacy"
+        );
+    }
+
+    #[test]
+    fn search_qualifiers_skip_inclusions_with_globs() {
+        let filter = RepoFilter::new(
+            strings(&["work/*", "!work/legacy"]),
+            vec![],
+            strings(&["oss"]),
+        );
+
+        assert_eq!(filter.search_qualifiers(), "-repo:work/legacy");
+    }
+}
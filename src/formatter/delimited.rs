@@ -0,0 +1,228 @@
+use super::Section;
+use super::markdown::body_preview;
+use super::tree::group_by_repository;
+use crate::range::Zone;
+
+const HEADER: [&str; 9] = [
+    "host",
+    "user",
+    "repository",
+    "subject_title",
+    "subject_url",
+    "action",
+    "event_url",
+    "created_at",
+    "body_excerpt",
+];
+
+/// Action of the row standing in for a host whose activity could not be collected.
+const FAILED_ACTION: &str = "Failed";
+
+pub fn format_csv(sections: &[Section], zone: Zone) -> String {
+    format_delimited(sections, ',', zone)
+}
+
+pub fn format_tsv(sections: &[Section], zone: Zone) -> String {
+    format_delimited(sections, '\t', zone)
+}
+
+fn format_delimited(sections: &[Section], delimiter: char, zone: Zone) -> String {
+    let mut out = String::new();
+    push_record(&mut out, HEADER, delimiter);
+
+    for section in sections {
+        // A failed host gets a single row, so it cannot silently drop out of the report.
+        if let Some(error) = &section.error {
+            push_record(
+                &mut out,
+                [
+                    section.host.as_str(),
+                    section.user.as_deref().unwrap_or_default(),
+                    "",
+                    "",
+                    "",
+                    FAILED_ACTION,
+                    "",
+                    "",
+                    error,
+                ],
+                delimiter,
+            );
+            continue;
+        }
+
+        let repositories = group_by_repository(&section.items);
+        let items = repositories
+            .iter()
+            .flat_map(|repository| &repository.subjects)
+            .flat_map(|subject| &subject.events);
+        for item in items {
+            let created_at = zone.to_rfc3339(item.created_at);
+            let excerpt = item
+                .body
+                .as_deref()
+                .and_then(|body| body_preview(body, 1, ""))
+                .unwrap_or_default();
+            push_record(
+                &mut out,
+                [
+                    section.host.as_str(),
+                    section.user.as_deref().unwrap_or_default(),
+                    &item.repository,
+                    &item.subject_title,
+                    &item.subject_url,
+                    item.action_label(),
+                    &item.url,
+                    &created_at,
+                    &excerpt,
+                ],
+                delimiter,
+            );
+        }
+    }
+
+    out
+}
+
+fn push_record<const N: usize>(out: &mut String, fields: [&str; N], delimiter: char) {
+    for (index, field) in fields.iter().enumerate() {
+        if index > 0 {
+            out.push(delimiter);
+        }
+        push_field(out, field, delimiter);
+    }
+    out.push('\n');
+}
+
+/// Quotes a field RFC 4180 style when it contains the delimiter, a quote or a line break.
+fn push_field(out: &mut String, field: &str, delimiter: char) {
+    let needs_quotes = field
+        .chars()
+        .any(|c| c == delimiter || matches!(c, '"' | '\n' | '\r'));
+    if !needs_quotes {
+        out.push_str(field);
+        return;
+    }
+
+    out.push('"');
+    out.push_str(&field.replace('"', "\"\""));
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::{EventItem, EventKind};
+    use chrono::TimeZone;
+
+    fn sample_item() -> EventItem {
+        EventItem {
+            kind: EventKind::IssueComment,
+            created_at: chrono::Utc.with_ymd_and_hms(2025, 1, 1, 12, 30, 0).unwrap(),
+            url: "https://example.test/comment/1".to_string(),
+            body: Some("hello, \"world\"\nsecond line".to_string()),
+            repository: "o/r".to_string(),
+            subject_title: "Fix a, b\tand c".to_string(),
+            subject_url: "https://example.test/issue/1".to_string(),
+            diff_stat: None,
+            review_state: None,
+        }
+    }
+
+    #[test]
+    fn format_csv_quotes_commas_and_quotes() {
+        let out = format_csv(
+            &[Section {
+                host: "github.com".to_string(),
+                user: None,
+                items: vec![sample_item()],
+                error: None,
+            }],
+            Zone::UTC,
+        );
+        let lines: Vec<&str> = out.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "host,user,repository,subject_title,subject_url,action,event_url,created_at,body_excerpt"
+        );
+        assert_eq!(
+            lines[1],
+            "github.com,,o/r,\"Fix a, b\tand c\",https://example.test/issue/1,Comment,\
+             https://example.test/comment/1,2025-01-01T12:30:00+00:00,\"hello, \"\"world\"\" ...\""
+        );
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[test]
+    fn format_tsv_quotes_tabs_only() {
+        let out = format_tsv(
+            &[Section {
+                host: "github.com".to_string(),
+                user: None,
+                items: vec![sample_item()],
+                error: None,
+            }],
+            Zone::UTC,
+        );
+        let lines: Vec<&str> = out.lines().collect();
+
+        assert_eq!(
+            lines[1],
+            "github.com\t\to/r\t\"Fix a, b\tand c\"\thttps://example.test/issue/1\tComment\t\
+             https://example.test/comment/1\t2025-01-01T12:30:00+00:00\t\"hello, \"\"world\"\" ...\""
+        );
+    }
+
+    #[test]
+    fn format_csv_writes_timestamps_in_zone() {
+        let tokyo = crate::range::parse_zone("Asia/Tokyo").unwrap();
+        let out = format_csv(
+            &[Section {
+                host: "github.com".to_string(),
+                user: None,
+                items: vec![sample_item()],
+                error: None,
+            }],
+            tokyo,
+        );
+        assert!(out.contains(",2025-01-01T21:30:00+09:00,"));
+    }
+
+    #[test]
+    fn format_csv_writes_a_row_for_failed_hosts() {
+        let error = anyhow::anyhow!("connection refused").context("GraphQL query failed");
+        let out = format_csv(
+            &[Section::failed("ghe.example.com".to_string(), &error)],
+            Zone::UTC,
+        );
+        let lines: Vec<&str> = out.lines().collect();
+
+        assert_eq!(
+            lines[1],
+            "ghe.example.com,,,,,Failed,,,GraphQL query failed: connection refused"
+        );
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[test]
+    fn push_field_quotes_line_breaks() {
+        let mut out = String::new();
+        push_field(&mut out, "line 1\r\nline 2", ',');
+        assert_eq!(out, "\"line 1\r\nline 2\"");
+    }
+
+    #[test]
+    fn format_csv_without_events_has_header_only() {
+        let out = format_csv(
+            &[Section {
+                host: "github.com".to_string(),
+                user: None,
+                items: vec![],
+                error: None,
+            }],
+            Zone::UTC,
+        );
+        assert_eq!(out.lines().count(), 1);
+    }
+}
@@ -0,0 +1,191 @@
+use super::Section;
+use super::markdown::{body_preview, should_include_event_url};
+use super::tree::group_by_repository;
+use crate::github::EventItem;
+use crate::range::Zone;
+
+const STYLE: &str = "\
+body { font-family: -apple-system, BlinkMacSystemFont, \"Segoe UI\", Helvetica, Arial, sans-serif; \
+line-height: 1.5; margin: 2rem auto; max-width: 60rem; padding: 0 1rem; color: #1f2328; }
+h1 { border-bottom: 1px solid #d1d9e0; padding-bottom: .3em; }
+h2 { margin-top: 1.5em; }
+h3 { font-size: 1em; margin-bottom: .25em; }
+a { color: #0969da; text-decoration: none; }
+a:hover { text-decoration: underline; }
+ul { margin-top: 0; padding-left: 1.5em; }
+time { color: #59636e; font-variant-numeric: tabular-nums; }
+details { margin: .25em 0 .5em; }
+summary { color: #59636e; cursor: pointer; }
+.body { white-space: pre-wrap; border-left: .25em solid #d1d9e0; padding: 0 1em; color: #59636e; }
+.empty { color: #59636e; font-style: italic; }
+.error { color: #d1242f; }
+";
+
+pub fn format_html(sections: &[Section], zone: Zone) -> String {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<title>gh-furik report</title>\n");
+    out.push_str(&format!("<style>\n{STYLE}</style>\n"));
+    out.push_str("</head>\n<body>\n");
+
+    for section in sections {
+        out.push_str("<section>\n");
+        out.push_str(&format!("<h1>{}</h1>\n", escape(&section.title())));
+
+        if let Some(error) = &section.error {
+            out.push_str(&format!(
+                "<p class=\"error\">Failed to collect activity: {}</p>\n",
+                escape(error)
+            ));
+        } else if section.items.is_empty() {
+            out.push_str("<p class=\"empty\">No activity found.</p>\n");
+        }
+
+        for repository in group_by_repository(&section.items) {
+            out.push_str(&format!("<h2>{}</h2>\n", escape(repository.name)));
+            for subject in repository.subjects {
+                out.push_str(&format!(
+                    "<h3><a href=\"{}\">{}</a></h3>\n<ul>\n",
+                    escape(subject.url),
+                    escape(subject.title)
+                ));
+                for item in subject.events {
+                    push_event(&mut out, item, zone);
+                }
+                out.push_str("</ul>\n");
+            }
+        }
+
+        out.push_str("</section>\n");
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn push_event(out: &mut String, item: &EventItem, zone: Zone) {
+    let date = zone.date_of(item.created_at);
+    let action_label = item.action_label();
+
+    out.push_str(&format!(
+        "<li><time datetime=\"{}\">{date}</time> ",
+        zone.to_rfc3339(item.created_at)
+    ));
+    if should_include_event_url(action_label) {
+        out.push_str(&format!(
+            "<a href=\"{}\">{}</a>",
+            escape(&item.url),
+            escape(action_label)
+        ));
+    } else {
+        out.push_str(&escape(action_label));
+    }
+    if let Some(stat) = item.diff_stat {
+        out.push_str(&format!(" (+{} -{})", stat.additions, stat.deletions));
+    }
+
+    if let Some(body) = item.body.as_deref()
+        && let Some(summary) = body_preview(body, 1, "")
+    {
+        out.push_str(&format!(
+            "\n<details><summary>{}</summary><div class=\"body\">{}</div></details>\n",
+            escape(&summary),
+            escape(body.trim())
+        ));
+    }
+    out.push_str("</li>\n");
+}
+
+fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::EventKind;
+    use chrono::TimeZone;
+
+    fn item(kind: EventKind, body: Option<&str>) -> EventItem {
+        EventItem {
+            kind,
+            created_at: chrono::Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            url: "https://example.test/comment/1".to_string(),
+            body: body.map(str::to_string),
+            repository: "o/r".to_string(),
+            subject_title: "Use <details> & \"quotes\"".to_string(),
+            subject_url: "https://example.test/issue/1".to_string(),
+            diff_stat: None,
+            review_state: None,
+        }
+    }
+
+    #[test]
+    fn format_html_renders_hierarchy_with_links() {
+        let out = format_html(
+            &[Section {
+                host: "github.com".to_string(),
+                user: None,
+                items: vec![item(
+                    EventKind::IssueComment,
+                    Some("<script>alert(1)</script>"),
+                )],
+                error: None,
+            }],
+            Zone::UTC,
+        );
+
+        assert!(out.starts_with("<!DOCTYPE html>"));
+        assert!(out.contains("<style>"));
+        assert!(out.contains("<h1>github.com</h1>"));
+        assert!(out.contains("<h2>o/r</h2>"));
+        assert!(out.contains(
+            "<h3><a href=\"https://example.test/issue/1\">Use &lt;details&gt; &amp; &quot;quotes&quot;</a></h3>"
+        ));
+        assert!(out.contains("<a href=\"https://example.test/comment/1\">Comment</a>"));
+        assert!(out.contains("<details><summary>&lt;script&gt;alert(1)&lt;/script&gt;</summary>"));
+        assert!(!out.contains("<script>"));
+    }
+
+    #[test]
+    fn format_html_merged_event_omits_event_link() {
+        let out = format_html(
+            &[Section {
+                host: "github.com".to_string(),
+                user: Some("alice".to_string()),
+                items: vec![item(EventKind::PullRequestMerged, None)],
+                error: None,
+            }],
+            Zone::UTC,
+        );
+
+        assert!(out.contains("<h1>alice (github.com)</h1>"));
+        assert!(out.contains("2025-01-01</time> Merged</li>"));
+        assert!(!out.contains("<details>"));
+    }
+
+    #[test]
+    fn format_html_empty_host() {
+        let out = format_html(
+            &[Section {
+                host: "github.com".to_string(),
+                user: None,
+                items: vec![],
+                error: None,
+            }],
+            Zone::UTC,
+        );
+        assert!(out.contains("No activity found."));
+    }
+}
@@ -0,0 +1,318 @@
+use super::Section;
+use crate::github::{EventItem, ReviewState};
+
+/// Version of the JSON report schema.
+///
+/// Bump this whenever a field is added, removed or renamed, or when the set of
+/// `kind` values changes, so downstream tooling can detect the difference.
+pub const SCHEMA_VERSION: u32 = 8;
+
+#[derive(serde::Serialize)]
+struct JsonReport<'a> {
+    schema_version: u32,
+    hosts: Vec<JsonHost<'a>>,
+}
+
+#[derive(serde::Serialize)]
+struct JsonHost<'a> {
+    host: &'a str,
+    user: Option<&'a str>,
+    error: Option<&'a str>,
+    events: Vec<JsonEvent<'a>>,
+}
+
+#[derive(serde::Serialize)]
+struct NdjsonEvent<'a> {
+    schema_version: u32,
+    host: &'a str,
+    user: Option<&'a str>,
+    #[serde(flatten)]
+    event: JsonEvent<'a>,
+}
+
+/// Last line written for a host whose activity could not be collected; any events already
+/// written for it are incomplete.
+#[derive(serde::Serialize)]
+struct NdjsonFailure<'a> {
+    schema_version: u32,
+    host: &'a str,
+    user: Option<&'a str>,
+    error: &'a str,
+}
+
+#[derive(serde::Serialize)]
+pub(super) struct JsonEvent<'a> {
+    kind: &'static str,
+    action: &'static str,
+    created_at: chrono::DateTime<chrono::Utc>,
+    url: &'a str,
+    repository: &'a str,
+    subject_title: &'a str,
+    subject_url: &'a str,
+    body: Option<&'a str>,
+    additions: Option<u64>,
+    deletions: Option<u64>,
+    review_state: Option<&'static str>,
+}
+
+impl<'a> From<&'a EventItem> for JsonEvent<'a> {
+    fn from(item: &'a EventItem) -> Self {
+        Self {
+            kind: item.kind.name(),
+            action: item.action_label(),
+            created_at: item.created_at,
+            url: &item.url,
+            repository: &item.repository,
+            subject_title: &item.subject_title,
+            subject_url: &item.subject_url,
+            body: item.body.as_deref(),
+            additions: item.diff_stat.map(|stat| stat.additions),
+            deletions: item.diff_stat.map(|stat| stat.deletions),
+            review_state: item.review_state.as_ref().map(ReviewState::name),
+        }
+    }
+}
+
+pub fn format_json(sections: &[Section]) -> String {
+    let report = JsonReport {
+        schema_version: SCHEMA_VERSION,
+        hosts: sections
+            .iter()
+            .map(|section| {
+                let mut sorted: Vec<&EventItem> = section.items.iter().collect();
+                sorted.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.url.cmp(&b.url)));
+                JsonHost {
+                    host: &section.host,
+                    user: section.user.as_deref(),
+                    error: section.error.as_deref(),
+                    events: sorted.into_iter().map(JsonEvent::from).collect(),
+                }
+            })
+            .collect(),
+    };
+
+    let mut out =
+        serde_json::to_string_pretty(&report).expect("JSON report serialization cannot fail");
+    out.push('\n');
+    out
+}
+
+/// Formats one JSON object per line, each tagged with its host and user.
+///
+/// A failed section becomes a single line with an `error` field instead of events.
+pub fn format_ndjson(section: &Section) -> String {
+    if let Some(error) = &section.error {
+        let line = NdjsonFailure {
+            schema_version: SCHEMA_VERSION,
+            host: &section.host,
+            user: section.user.as_deref(),
+            error,
+        };
+        let mut out = serde_json::to_string(&line).expect("JSON failure serialization cannot fail");
+        out.push('\n');
+        return out;
+    }
+
+    let mut sorted: Vec<&EventItem> = section.items.iter().collect();
+    sorted.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.url.cmp(&b.url)));
+
+    let mut out = String::new();
+    for item in sorted {
+        let line = NdjsonEvent {
+            schema_version: SCHEMA_VERSION,
+            host: &section.host,
+            user: section.user.as_deref(),
+            event: JsonEvent::from(item),
+        };
+        out.push_str(&serde_json::to_string(&line).expect("JSON event serialization cannot fail"));
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::EventKind;
+    use chrono::TimeZone;
+
+    fn section(host: &str, items: Vec<EventItem>) -> Section {
+        Section {
+            host: host.to_string(),
+            user: None,
+            items,
+            error: None,
+        }
+    }
+
+    fn item(kind: EventKind, day: u32, url: &str) -> EventItem {
+        EventItem {
+            kind,
+            created_at: chrono::Utc.with_ymd_and_hms(2025, 1, day, 0, 0, 0).unwrap(),
+            url: url.to_string(),
+            body: None,
+            repository: "o/r".to_string(),
+            subject_title: "PR A".to_string(),
+            subject_url: "https://example.test/pull/1".to_string(),
+            diff_stat: None,
+            review_state: None,
+        }
+    }
+
+    #[test]
+    fn format_json_groups_events_by_host_with_schema_version() {
+        let out = format_json(&[
+            section(
+                "github.com",
+                vec![item(
+                    EventKind::PullRequestMerged,
+                    2,
+                    "https://example.test/pull/1",
+                )],
+            ),
+            section("ghe.example.com", vec![]),
+        ]);
+        let value: serde_json::Value = serde_json::from_str(&out).unwrap();
+
+        assert_eq!(value["schema_version"], SCHEMA_VERSION);
+        assert_eq!(value["hosts"][0]["host"], "github.com");
+        assert_eq!(value["hosts"][0]["user"], serde_json::Value::Null);
+        assert_eq!(value["hosts"][1]["host"], "ghe.example.com");
+        assert_eq!(value["hosts"][1]["events"], serde_json::json!([]));
+        assert_eq!(value["hosts"][0]["error"], serde_json::Value::Null);
+
+        let event = &value["hosts"][0]["events"][0];
+        assert_eq!(event["kind"], "pull_request_merged");
+        assert_eq!(event["action"], "Merged");
+        assert_eq!(event["created_at"], "2025-01-02T00:00:00Z");
+        assert_eq!(event["repository"], "o/r");
+        assert_eq!(event["subject_title"], "PR A");
+        assert_eq!(event["subject_url"], "https://example.test/pull/1");
+        assert_eq!(event["body"], serde_json::Value::Null);
+        assert_eq!(event["additions"], serde_json::Value::Null);
+        assert_eq!(event["review_state"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn format_json_labels_reviews_by_state() {
+        let review = EventItem {
+            review_state: Some(ReviewState::ChangesRequested),
+            ..item(
+                EventKind::PullRequestReview,
+                1,
+                "https://example.test/review/1",
+            )
+        };
+        let out = format_json(&[section("github.com", vec![review])]);
+        let value: serde_json::Value = serde_json::from_str(&out).unwrap();
+
+        let event = &value["hosts"][0]["events"][0];
+        assert_eq!(event["kind"], "pull_request_review");
+        assert_eq!(event["action"], "Requested changes");
+        assert_eq!(event["review_state"], "changes_requested");
+    }
+
+    #[test]
+    fn format_json_includes_commit_diff_stat() {
+        let commit = EventItem {
+            diff_stat: Some(crate::github::DiffStat {
+                additions: 12,
+                deletions: 3,
+            }),
+            ..item(EventKind::Commit, 1, "https://example.test/commit/abc")
+        };
+        let out = format_json(&[section("github.com", vec![commit])]);
+        let value: serde_json::Value = serde_json::from_str(&out).unwrap();
+
+        let event = &value["hosts"][0]["events"][0];
+        assert_eq!(event["kind"], "commit");
+        assert_eq!(event["additions"], 12);
+        assert_eq!(event["deletions"], 3);
+    }
+
+    #[test]
+    fn format_json_reports_failed_hosts() {
+        let error = anyhow::anyhow!("connection refused").context("GraphQL query failed");
+        let out = format_json(&[Section::failed("ghe.example.com".to_string(), &error)]);
+        let value: serde_json::Value = serde_json::from_str(&out).unwrap();
+
+        assert_eq!(value["hosts"][0]["host"], "ghe.example.com");
+        assert_eq!(
+            value["hosts"][0]["error"],
+            "GraphQL query failed: connection refused"
+        );
+        assert_eq!(value["hosts"][0]["events"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn format_ndjson_reports_failed_hosts_on_one_line() {
+        let error = anyhow::anyhow!("connection refused");
+        let out = format_ndjson(&Section::failed("ghe.example.com".to_string(), &error));
+        let lines: Vec<serde_json::Value> = out
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(
+            lines,
+            [serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "host": "ghe.example.com",
+                "user": null,
+                "error": "connection refused"
+            })]
+        );
+    }
+
+    #[test]
+    fn format_json_sorts_events_by_time() {
+        let out = format_json(&[section(
+            "github.com",
+            vec![
+                item(EventKind::IssueComment, 3, "https://example.test/comment/2"),
+                item(EventKind::IssueComment, 1, "https://example.test/comment/1"),
+            ],
+        )]);
+        let value: serde_json::Value = serde_json::from_str(&out).unwrap();
+
+        assert_eq!(
+            value["hosts"][0]["events"][0]["url"],
+            "https://example.test/comment/1"
+        );
+        assert_eq!(
+            value["hosts"][0]["events"][1]["url"],
+            "https://example.test/comment/2"
+        );
+    }
+
+    #[test]
+    fn format_ndjson_writes_one_tagged_event_per_line() {
+        let out = format_ndjson(&Section {
+            user: Some("alice".to_string()),
+            ..section(
+                "ghe.example.com",
+                vec![
+                    item(EventKind::IssueComment, 3, "https://example.test/comment/2"),
+                    item(EventKind::IssueComment, 1, "https://example.test/comment/1"),
+                ],
+            )
+        });
+        let lines: Vec<serde_json::Value> = out
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["schema_version"], SCHEMA_VERSION);
+        assert_eq!(lines[0]["host"], "ghe.example.com");
+        assert_eq!(lines[0]["user"], "alice");
+        assert_eq!(lines[0]["kind"], "issue_comment");
+        assert_eq!(lines[0]["url"], "https://example.test/comment/1");
+        assert_eq!(lines[1]["url"], "https://example.test/comment/2");
+    }
+
+    #[test]
+    fn format_ndjson_empty_is_empty() {
+        assert_eq!(format_ndjson(&section("github.com", vec![])), "");
+    }
+}
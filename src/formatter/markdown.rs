@@ -1,8 +1,10 @@
+use super::tree::group_by_repository;
 use crate::github::EventItem;
+use crate::range::Zone;
 
 const COMMENT_PREVIEW_MAX_LEN: usize = 80;
 
-pub fn format_markdown(host: &str, items: &[EventItem], compact: bool) -> String {
+pub fn format_markdown(host: &str, items: &[EventItem], compact: bool, zone: Zone) -> String {
     let mut out = String::new();
     out.push_str(&format!("# {host}\n\n"));
 
@@ -11,72 +13,70 @@ pub fn format_markdown(host: &str, items: &[EventItem], compact: bool) -> String
         return out;
     }
 
-    let mut sorted: Vec<&EventItem> = items.iter().collect();
-    sorted.sort_by(|a, b| {
-        a.repository
-            .cmp(&b.repository)
-            .then(a.subject_url.cmp(&b.subject_url))
-            .then(a.created_at.cmp(&b.created_at))
-    });
-
-    let mut current_repo: Option<&str> = None;
-    let mut current_subject: Option<&str> = None;
-
-    for item in sorted {
-        if current_repo != Some(item.repository.as_str()) {
-            current_repo = Some(item.repository.as_str());
-            current_subject = None;
-            if !out.ends_with("\n\n") {
-                out.push('\n');
-            }
-            out.push_str(&format!("## {}\n\n", item.repository));
+    for repository in group_by_repository(items) {
+        if !out.ends_with("\n\n") {
+            out.push('\n');
         }
+        out.push_str(&format!("## {}\n\n", repository.name));
 
-        if current_subject != Some(item.subject_url.as_str()) {
-            current_subject = Some(item.subject_url.as_str());
+        for subject in repository.subjects {
             if !compact && !out.ends_with("\n\n") {
                 out.push('\n');
             }
             if compact {
-                out.push_str(&format!("- {} {}\n", item.subject_title, item.subject_url));
+                out.push_str(&format!("- {} {}\n", subject.title, subject.url));
             } else {
-                out.push_str(&format!(
-                    "### {} {}\n\n",
-                    item.subject_title, item.subject_url
-                ));
+                out.push_str(&format!("### {} {}\n\n", subject.title, subject.url));
             }
-        }
 
-        let date = item.created_at.date_naive();
-        let action_label = item.kind.action_label();
-        if compact {
-            if should_include_event_url(action_label) {
-                out.push_str(&format!("  - {date} {} {}\n", action_label, item.url));
-            } else {
-                out.push_str(&format!("  - {date} {}\n", action_label));
+            for item in subject.events {
+                let date = zone.date_of(item.created_at);
+                let action_label = item.action_label();
+                let diff_stat = item
+                    .diff_stat
+                    .map(|stat| format!(" (+{} -{})", stat.additions, stat.deletions))
+                    .unwrap_or_default();
+                if compact {
+                    if should_include_event_url(action_label) {
+                        out.push_str(&format!(
+                            "  - {date} {} {}{diff_stat}\n",
+                            action_label, item.url
+                        ));
+                    } else {
+                        out.push_str(&format!("  - {date} {}{diff_stat}\n", action_label));
+                    }
+                } else if should_include_event_url(action_label) {
+                    out.push_str(&format!(
+                        "- {date} {} {}{diff_stat}\n",
+                        action_label, item.url
+                    ));
+                } else {
+                    out.push_str(&format!("- {date} {}{diff_stat}\n", action_label));
+                }
+
+                if let Some(body) = item.body.as_ref()
+                    && let Some(preview) = body_preview(
+                        body,
+                        preview_line_limit(action_label),
+                        if compact { "    > " } else { "  > " },
+                    )
+                {
+                    out.push_str(&preview);
+                    out.push('\n');
+                }
             }
-        } else if should_include_event_url(action_label) {
-            out.push_str(&format!("- {date} {} {}\n", action_label, item.url));
-        } else {
-            out.push_str(&format!("- {date} {}\n", action_label));
-        }
-
-        if let Some(body) = item.body.as_ref()
-            && let Some(preview) = body_preview(
-                body,
-                preview_line_limit(action_label),
-                if compact { "    > " } else { "  > " },
-            )
-        {
-            out.push_str(&preview);
-            out.push('\n');
         }
     }
 
     out
 }
 
-fn body_preview(body: &str, max_lines: usize, line_prefix: &str) -> Option<String> {
+/// Section shown in place of a host whose activity could not be collected.
+pub fn format_markdown_failure(host: &str, error: &str) -> String {
+    format!("# {host}\n\n_Failed to collect activity: {error}_\n")
+}
+
+pub(super) fn body_preview(body: &str, max_lines: usize, line_prefix: &str) -> Option<String> {
     if max_lines == 0 {
         return None;
     }
@@ -124,14 +124,14 @@ fn preview_line_limit(action_label: &str) -> usize {
     if action_label == "Opened" { 3 } else { 1 }
 }
 
-fn should_include_event_url(action_label: &str) -> bool {
-    !matches!(action_label, "Opened" | "Closed" | "Merged")
+pub(super) fn should_include_event_url(action_label: &str) -> bool {
+    !matches!(action_label, "Opened" | "Closed" | "Reopened" | "Merged")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::github::EventKind;
+    use crate::github::{EventKind, ReviewState};
     use chrono::TimeZone;
 
     fn sample_item() -> EventItem {
@@ -143,19 +143,30 @@ mod tests {
             repository: "o/r".to_string(),
             subject_title: "Issue A".to_string(),
             subject_url: "https://example.test/issue/1".to_string(),
+            diff_stat: None,
+            review_state: None,
         }
     }
 
     #[test]
     fn format_markdown_empty() {
-        let out = format_markdown("github.com", &[], false);
+        let out = format_markdown("github.com", &[], false, Zone::UTC);
         assert!(out.contains("_No activity found._"));
     }
 
+    #[test]
+    fn format_markdown_failure_names_the_error() {
+        let out = format_markdown_failure("ghe.example.com", "connection refused");
+        assert_eq!(
+            out,
+            "# ghe.example.com\n\n_Failed to collect activity: connection refused_\n"
+        );
+    }
+
     #[test]
     fn format_markdown_single_item() {
         let item = sample_item();
-        let out = format_markdown("github.com", &[item], false);
+        let out = format_markdown("github.com", &[item], false, Zone::UTC);
         assert!(out.contains("# github.com"));
         assert!(out.contains("## o/r"));
         assert!(out.contains("### Issue A https://example.test/issue/1"));
@@ -167,7 +178,7 @@ mod tests {
     #[test]
     fn format_markdown_compact_single_item() {
         let item = sample_item();
-        let out = format_markdown("github.com", &[item], true);
+        let out = format_markdown("github.com", &[item], true, Zone::UTC);
         assert!(out.contains("# github.com"));
         assert!(out.contains("## o/r"));
         assert!(out.contains("- Issue A https://example.test/issue/1"));
@@ -176,6 +187,55 @@ mod tests {
         assert!(!out.contains("> world"));
     }
 
+    #[test]
+    fn format_markdown_commit_shows_diff_stat() {
+        let item = EventItem {
+            kind: EventKind::Commit,
+            url: "https://example.test/o/r/commit/abc".to_string(),
+            body: Some("Fix the parser".to_string()),
+            subject_title: "Commits".to_string(),
+            subject_url: "https://example.test/o/r/commits?author=me".to_string(),
+            diff_stat: Some(crate::github::DiffStat {
+                additions: 12,
+                deletions: 3,
+            }),
+            ..sample_item()
+        };
+        let out = format_markdown("github.com", &[item], false, Zone::UTC);
+
+        assert!(out.contains("## o/r\n\n### Commits https://example.test/o/r/commits?author=me"));
+        assert!(
+            out.contains("- 2025-01-01 Committed https://example.test/o/r/commit/abc (+12 -3)\n")
+        );
+        assert!(out.contains("  > Fix the parser"));
+    }
+
+    #[test]
+    fn format_markdown_labels_reviews_by_state() {
+        let review = |state, url: &str| EventItem {
+            kind: EventKind::PullRequestReview,
+            url: url.to_string(),
+            body: None,
+            review_state: Some(state),
+            ..sample_item()
+        };
+        let out = format_markdown(
+            "github.com",
+            &[
+                review(ReviewState::Approved, "https://example.test/review/1"),
+                review(
+                    ReviewState::ChangesRequested,
+                    "https://example.test/review/2",
+                ),
+            ],
+            false,
+            Zone::UTC,
+        );
+
+        assert!(out.contains("- 2025-01-01 Approved https://example.test/review/1\n"));
+        assert!(out.contains("- 2025-01-01 Requested changes https://example.test/review/2\n"));
+    }
+
     #[test]
     fn format_markdown_merged_event_omits_event_url() {
         let item = EventItem {
@@ -186,8 +246,10 @@ mod tests {
             repository: "o/r".to_string(),
             subject_title: "PR A".to_string(),
             subject_url: "https://example.test/pull/1".to_string(),
+            diff_stat: None,
+            review_state: None,
         };
-        let out = format_markdown("github.com", &[item], false);
+        let out = format_markdown("github.com", &[item], false, Zone::UTC);
 
         assert!(out.contains("### PR A https://example.test/pull/1"));
         assert!(out.contains("- 2025-01-02 Merged\n"));
@@ -204,8 +266,10 @@ mod tests {
             repository: "o/r".to_string(),
             subject_title: "PR A".to_string(),
             subject_url: "https://example.test/pull/1".to_string(),
+            diff_stat: None,
+            review_state: None,
         };
-        let out = format_markdown("github.com", &[item], true);
+        let out = format_markdown("github.com", &[item], true, Zone::UTC);
 
         assert!(out.contains("- PR A https://example.test/pull/1"));
         assert!(out.contains("  - 2025-01-02 Merged\n"));
@@ -225,8 +289,10 @@ mod tests {
             repository: "o/r".to_string(),
             subject_title: "PR B".to_string(),
             subject_url: "https://example.test/pull/2".to_string(),
+            diff_stat: None,
+            review_state: None,
         };
-        let out = format_markdown("github.com", &[item], false);
+        let out = format_markdown("github.com", &[item], false, Zone::UTC);
 
         assert!(out.contains("- 2025-01-03 Opened\n"));
         assert!(!out.contains("- 2025-01-03 Opened https://example.test/pr-event/2"));
@@ -246,13 +312,32 @@ mod tests {
             repository: "o/r".to_string(),
             subject_title: "PR B".to_string(),
             subject_url: "https://example.test/pull/2".to_string(),
+            diff_stat: None,
+            review_state: None,
         };
-        let out = format_markdown("github.com", &[item], true);
+        let out = format_markdown("github.com", &[item], true, Zone::UTC);
 
         assert!(out.contains("    > line 1\n    > line 2\n    > line 3"));
         assert!(!out.contains("\n  > line 2"));
     }
 
+    #[test]
+    fn format_markdown_shows_dates_in_zone() {
+        let item = EventItem {
+            created_at: chrono::Utc
+                .with_ymd_and_hms(2024, 12, 31, 23, 30, 0)
+                .unwrap(),
+            ..sample_item()
+        };
+        let tokyo = crate::range::parse_zone("Asia/Tokyo").unwrap();
+
+        let out = format_markdown("github.com", std::slice::from_ref(&item), false, tokyo);
+        assert!(out.contains("- 2025-01-01 Comment"));
+
+        let out = format_markdown("github.com", &[item], false, Zone::UTC);
+        assert!(out.contains("- 2024-12-31 Comment"));
+    }
+
     #[test]
     fn body_preview_shows_both_truncation_and_more_indicator() {
         let body = "123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890\nsecond";
@@ -274,8 +359,10 @@ mod tests {
             repository: "o/r".to_string(),
             subject_title: "Issue C".to_string(),
             subject_url: "https://example.test/issues/3".to_string(),
+            diff_stat: None,
+            review_state: None,
         };
-        let out = format_markdown("github.com", &[item], false);
+        let out = format_markdown("github.com", &[item], false, Zone::UTC);
 
         assert!(out.contains("- 2025-01-04 Opened\n"));
         assert!(out.contains("  > i line 1"));
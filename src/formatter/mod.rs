@@ -0,0 +1,43 @@
+mod delimited;
+mod html;
+mod json;
+mod markdown;
+mod template;
+mod tree;
+
+pub use delimited::{format_csv, format_tsv};
+pub use html::format_html;
+pub use json::{format_json, format_ndjson};
+pub use markdown::{format_markdown, format_markdown_failure};
+pub use template::format_template;
+
+use crate::github::EventItem;
+
+/// Events collected from one host, for one person when a report covers several.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub host: String,
+    pub user: Option<String>,
+    pub items: Vec<EventItem>,
+    /// Why collecting this host failed; such sections have no items.
+    pub error: Option<String>,
+}
+
+impl Section {
+    pub fn failed(host: String, error: &anyhow::Error) -> Self {
+        Self {
+            host,
+            user: None,
+            items: Vec::new(),
+            error: Some(format!("{error:#}")),
+        }
+    }
+
+    /// Heading used by the human-readable formats.
+    pub fn title(&self) -> String {
+        match &self.user {
+            Some(user) => format!("{user} ({})", self.host),
+            None => self.host.clone(),
+        }
+    }
+}
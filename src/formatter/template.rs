@@ -0,0 +1,201 @@
+use super::Section;
+use super::json::{JsonEvent, SCHEMA_VERSION};
+use super::tree::group_by_repository;
+use crate::github::EventItem;
+use crate::range::Zone;
+use anyhow::Context;
+use std::path::Path;
+
+#[derive(serde::Serialize)]
+struct TemplateContext<'a> {
+    schema_version: u32,
+    hosts: Vec<TemplateHost<'a>>,
+}
+
+#[derive(serde::Serialize)]
+struct TemplateHost<'a> {
+    host: &'a str,
+    user: Option<&'a str>,
+    title: String,
+    error: Option<&'a str>,
+    events: Vec<TemplateEvent<'a>>,
+    repositories: Vec<TemplateRepository<'a>>,
+}
+
+#[derive(serde::Serialize)]
+struct TemplateRepository<'a> {
+    name: &'a str,
+    subjects: Vec<TemplateSubject<'a>>,
+}
+
+#[derive(serde::Serialize)]
+struct TemplateSubject<'a> {
+    title: &'a str,
+    url: &'a str,
+    events: Vec<TemplateEvent<'a>>,
+}
+
+#[derive(serde::Serialize)]
+struct TemplateEvent<'a> {
+    date: chrono::NaiveDate,
+    #[serde(flatten)]
+    event: JsonEvent<'a>,
+}
+
+impl<'a> TemplateEvent<'a> {
+    fn new(item: &'a EventItem, zone: Zone) -> Self {
+        Self {
+            date: zone.date_of(item.created_at),
+            event: JsonEvent::from(item),
+        }
+    }
+}
+
+pub fn format_template(path: &Path, sections: &[Section], zone: Zone) -> anyhow::Result<String> {
+    let source = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read template {}", path.display()))?;
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("template");
+    render_template(name, source, sections, zone)
+}
+
+fn render_template(
+    name: &str,
+    source: String,
+    sections: &[Section],
+    zone: Zone,
+) -> anyhow::Result<String> {
+    let mut env = minijinja::Environment::new();
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    env.add_template_owned(name.to_string(), source)
+        .with_context(|| format!("failed to parse template {name}"))?;
+
+    let context = TemplateContext {
+        schema_version: SCHEMA_VERSION,
+        hosts: sections
+            .iter()
+            .map(|section| {
+                let mut sorted: Vec<&EventItem> = section.items.iter().collect();
+                sorted.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.url.cmp(&b.url)));
+                TemplateHost {
+                    host: &section.host,
+                    user: section.user.as_deref(),
+                    title: section.title(),
+                    error: section.error.as_deref(),
+                    events: sorted
+                        .into_iter()
+                        .map(|item| TemplateEvent::new(item, zone))
+                        .collect(),
+                    repositories: group_by_repository(&section.items)
+                        .into_iter()
+                        .map(|repository| TemplateRepository {
+                            name: repository.name,
+                            subjects: repository
+                                .subjects
+                                .into_iter()
+                                .map(|subject| TemplateSubject {
+                                    title: subject.title,
+                                    url: subject.url,
+                                    events: subject
+                                        .events
+                                        .into_iter()
+                                        .map(|item| TemplateEvent::new(item, zone))
+                                        .collect(),
+                                })
+                                .collect(),
+                        })
+                        .collect(),
+                }
+            })
+            .collect(),
+    };
+
+    env.get_template(name)?
+        .render(context)
+        .with_context(|| format!("failed to render template {name}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::EventKind;
+    use chrono::TimeZone;
+
+    fn sample_item() -> EventItem {
+        EventItem {
+            kind: EventKind::PullRequestReview,
+            created_at: chrono::Utc.with_ymd_and_hms(2025, 1, 2, 3, 4, 5).unwrap(),
+            url: "https://example.test/review/1".to_string(),
+            body: Some("<b>LGTM</b>".to_string()),
+            repository: "o/r".to_string(),
+            subject_title: "PR A".to_string(),
+            subject_url: "https://example.test/pull/1".to_string(),
+            diff_stat: None,
+            review_state: None,
+        }
+    }
+
+    #[test]
+    fn render_template_walks_hosts_repositories_and_subjects() {
+        let source = "\
+{% for host in hosts %}
+{{ host.host }}
+{% for repo in host.repositories %}
+* {{ repo.name }}
+{% for subject in repo.subjects %}
+  * {{ subject.title }} ({{ subject.url }})
+{% for event in subject.events %}
+    * {{ event.date }} {{ event.action }} {{ event.kind }}
+{% endfor %}
+{% endfor %}
+{% endfor %}
+{% endfor %}";
+
+        let out = render_template(
+            "report.tmpl",
+            source.to_string(),
+            &[Section {
+                host: "github.com".to_string(),
+                user: None,
+                items: vec![sample_item()],
+                error: None,
+            }],
+            Zone::UTC,
+        )
+        .unwrap();
+
+        assert_eq!(
+            out,
+            "github.com\n* o/r\n  * PR A (https://example.test/pull/1)\n    * 2025-01-02 Reviewed pull_request_review\n"
+        );
+    }
+
+    #[test]
+    fn render_template_escapes_html_templates_only() {
+        let sections = [Section {
+            host: "github.com".to_string(),
+            user: None,
+            items: vec![sample_item()],
+            error: None,
+        }];
+        let source = "{% for event in hosts[0].events %}{{ event.body }}{% endfor %}";
+
+        let text =
+            render_template("report.tmpl", source.to_string(), &sections, Zone::UTC).unwrap();
+        let html =
+            render_template("report.html", source.to_string(), &sections, Zone::UTC).unwrap();
+
+        assert_eq!(text, "<b>LGTM</b>");
+        assert_eq!(html, "&lt;b&gt;LGTM&lt;&#x2f;b&gt;");
+    }
+
+    #[test]
+    fn render_template_reports_syntax_errors() {
+        let error =
+            render_template("broken.tmpl", "{% for %}".to_string(), &[], Zone::UTC).unwrap_err();
+        assert!(error.to_string().contains("broken.tmpl"));
+    }
+}
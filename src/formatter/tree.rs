@@ -0,0 +1,52 @@
+use crate::github::EventItem;
+
+/// Events of one host grouped the same way as the Markdown report.
+pub(super) struct RepositoryGroup<'a> {
+    pub name: &'a str,
+    pub subjects: Vec<SubjectGroup<'a>>,
+}
+
+pub(super) struct SubjectGroup<'a> {
+    pub title: &'a str,
+    pub url: &'a str,
+    pub events: Vec<&'a EventItem>,
+}
+
+pub(super) fn group_by_repository(items: &[EventItem]) -> Vec<RepositoryGroup<'_>> {
+    let mut sorted: Vec<&EventItem> = items.iter().collect();
+    sorted.sort_by(|a, b| {
+        a.repository
+            .cmp(&b.repository)
+            .then(a.subject_url.cmp(&b.subject_url))
+            .then(a.created_at.cmp(&b.created_at))
+    });
+
+    let mut repositories: Vec<RepositoryGroup<'_>> = Vec::new();
+    for item in sorted {
+        if repositories.last().map(|repo| repo.name) != Some(item.repository.as_str()) {
+            repositories.push(RepositoryGroup {
+                name: &item.repository,
+                subjects: Vec::new(),
+            });
+        }
+        let subjects = &mut repositories
+            .last_mut()
+            .expect("repository group was just pushed")
+            .subjects;
+
+        if subjects.last().map(|subject| subject.url) != Some(item.subject_url.as_str()) {
+            subjects.push(SubjectGroup {
+                title: &item.subject_title,
+                url: &item.subject_url,
+                events: Vec::new(),
+            });
+        }
+        subjects
+            .last_mut()
+            .expect("subject group was just pushed")
+            .events
+            .push(item);
+    }
+
+    repositories
+}
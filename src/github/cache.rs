@@ -0,0 +1,270 @@
+use super::graphql::GraphqlResponse;
+use anyhow::Context;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// GraphQL responses stored on disk, keyed by host, token, query text and variables.
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    dir: PathBuf,
+    ttl: Duration,
+    refresh: bool,
+    namespace: String,
+}
+
+impl ResponseCache {
+    /// A cache under `dir` whose entries are reused for `ttl`.
+    ///
+    /// With `refresh`, existing entries are ignored but fresh responses are still stored.
+    pub fn new(dir: PathBuf, ttl: Duration, refresh: bool) -> Self {
+        Self {
+            dir,
+            ttl,
+            refresh,
+            namespace: String::new(),
+        }
+    }
+
+    /// `$XDG_CACHE_HOME/gh-furik`, falling back to `~/.cache/gh-furik`.
+    pub fn default_dir() -> Option<PathBuf> {
+        crate::xdg::app_dir("XDG_CACHE_HOME", ".cache")
+    }
+
+    /// Restricts the cache to responses fetched from `host` with `token`, so that different
+    /// accounts never see each other's responses.
+    pub(crate) fn scoped(&self, host: &str, token: &str) -> Self {
+        Self {
+            dir: self.dir.join(host_dir_name(host)),
+            namespace: hex_digest(token.as_bytes()),
+            ..self.clone()
+        }
+    }
+
+    /// Deletes entries of every host that are older than `max_age`, so that entries which are
+    /// never requested again do not pile up. Returns how many were deleted.
+    pub fn prune(&self, max_age: Duration) -> anyhow::Result<usize> {
+        let hosts = match std::fs::read_dir(&self.dir) {
+            Ok(hosts) => hosts,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(error) => {
+                return Err(error).with_context(|| {
+                    format!("failed to read cache directory {}", self.dir.display())
+                });
+            }
+        };
+
+        let mut deleted = 0;
+        for host in hosts.flatten().filter(|entry| entry.path().is_dir()) {
+            let Ok(entries) = std::fs::read_dir(host.path()) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                if is_older_than(&entry.path(), max_age)
+                    && std::fs::remove_file(entry.path()).is_ok()
+                {
+                    deleted += 1;
+                }
+            }
+        }
+        Ok(deleted)
+    }
+
+    /// Returns the stored response for `payload` unless it is missing, expired or being refreshed.
+    ///
+    /// Expired entries are deleted.
+    pub(crate) fn get(
+        &self,
+        payload: &serde_json::Value,
+    ) -> Option<GraphqlResponse<serde_json::Value>> {
+        if self.refresh {
+            return None;
+        }
+        let path = self.path(payload);
+        if is_older_than(&path, self.ttl) {
+            let _ = std::fs::remove_file(&path);
+            return None;
+        }
+        let body = std::fs::read_to_string(&path).ok()?;
+        serde_json::from_str(&body).ok()
+    }
+
+    /// Stores a response, replacing any previous entry atomically.
+    pub(crate) fn put(
+        &self,
+        payload: &serde_json::Value,
+        response: &GraphqlResponse<serde_json::Value>,
+    ) -> anyhow::Result<()> {
+        let path = self.path(payload);
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("failed to create cache directory {}", self.dir.display()))?;
+        let body = serde_json::to_string(response).context("failed to serialize response")?;
+        let temp = path.with_extension(format!("tmp{}", std::process::id()));
+        std::fs::write(&temp, body)
+            .and_then(|()| std::fs::rename(&temp, &path))
+            .with_context(|| format!("failed to write cache entry {}", path.display()))
+    }
+
+    fn path(&self, payload: &serde_json::Value) -> PathBuf {
+        let key = format!("{}\n{payload}", self.namespace);
+        self.dir
+            .join(format!("{}.json", hex_digest(key.as_bytes())))
+    }
+}
+
+/// Whether the file at `path` was last written at least `age` ago. Missing files are not.
+fn is_older_than(path: &Path, age: Duration) -> bool {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .is_ok_and(|modified| modified.elapsed().unwrap_or_default() >= age)
+}
+
+/// Directory name for `host`, safe on every platform.
+pub(crate) fn host_dir_name(host: &str) -> String {
+    host.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+pub(super) fn hex_digest(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use temp_env::with_vars;
+
+    /// Directory of the test cache `name`, which each test removes when it ends.
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("gh-furik-cache-test-{}-{name}", std::process::id()))
+    }
+
+    fn temp_cache(name: &str, ttl: Duration, refresh: bool) -> ResponseCache {
+        let dir = temp_dir(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        ResponseCache::new(dir, ttl, refresh).scoped("github.com", "token")
+    }
+
+    fn payload(after: &str) -> serde_json::Value {
+        serde_json::json!({ "query": "query { viewer { login } }", "variables": { "after": after } })
+    }
+
+    fn response(login: &str) -> GraphqlResponse<serde_json::Value> {
+        serde_json::from_value(serde_json::json!({ "data": { "viewer": { "login": login } } }))
+            .unwrap()
+    }
+
+    #[test]
+    fn get_returns_stored_response_for_the_same_payload_only() {
+        let cache = temp_cache("roundtrip", Duration::from_secs(60), false);
+        cache.put(&payload("a"), &response("alice")).unwrap();
+
+        let cached = cache.get(&payload("a")).unwrap();
+        assert_eq!(cached.data.unwrap()["viewer"]["login"], "alice");
+        assert!(cache.get(&payload("b")).is_none());
+
+        std::fs::remove_dir_all(temp_dir("roundtrip")).unwrap();
+    }
+
+    #[test]
+    fn get_ignores_expired_or_refreshed_entries() {
+        let expired = temp_cache("expired", Duration::ZERO, false);
+        expired.put(&payload("a"), &response("alice")).unwrap();
+        assert!(expired.get(&payload("a")).is_none());
+
+        let refreshed = temp_cache("refresh", Duration::from_secs(60), true);
+        refreshed.put(&payload("a"), &response("alice")).unwrap();
+        assert!(refreshed.get(&payload("a")).is_none());
+
+        std::fs::remove_dir_all(temp_dir("expired")).unwrap();
+        std::fs::remove_dir_all(temp_dir("refresh")).unwrap();
+    }
+
+    #[test]
+    fn expired_entries_are_deleted() {
+        let expired = temp_cache("delete-expired", Duration::ZERO, false);
+        expired.put(&payload("a"), &response("alice")).unwrap();
+        assert!(expired.get(&payload("a")).is_none());
+        assert!(!expired.path(&payload("a")).exists());
+
+        std::fs::remove_dir_all(temp_dir("delete-expired")).unwrap();
+    }
+
+    #[test]
+    fn prune_deletes_old_entries_of_every_host() {
+        let root = temp_dir("prune");
+        let _ = std::fs::remove_dir_all(&root);
+        let cache = ResponseCache::new(root.clone(), Duration::from_secs(60), false);
+        let github = cache.scoped("github.com", "token");
+        let ghe = cache.scoped("ghe.example.com", "token");
+        github.put(&payload("old"), &response("alice")).unwrap();
+        ghe.put(&payload("old"), &response("alice")).unwrap();
+        github.put(&payload("new"), &response("alice")).unwrap();
+        let week_ago = std::time::SystemTime::now() - Duration::from_secs(7 * 24 * 60 * 60);
+        for path in [github.path(&payload("old")), ghe.path(&payload("old"))] {
+            std::fs::File::options()
+                .write(true)
+                .open(path)
+                .and_then(|file| file.set_modified(week_ago))
+                .unwrap();
+        }
+
+        assert_eq!(cache.prune(Duration::from_secs(24 * 60 * 60)).unwrap(), 2);
+        assert!(github.get(&payload("old")).is_none());
+        assert!(github.get(&payload("new")).is_some());
+        std::fs::remove_dir_all(&root).unwrap();
+        assert_eq!(cache.prune(Duration::ZERO).unwrap(), 0);
+    }
+
+    #[test]
+    fn scoped_caches_do_not_share_entries() {
+        let cache = temp_cache("scoped", Duration::from_secs(60), false);
+        cache.put(&payload("a"), &response("alice")).unwrap();
+
+        let other_token = ResponseCache {
+            namespace: hex_digest(b"other"),
+            ..cache.clone()
+        };
+        assert!(other_token.get(&payload("a")).is_none());
+        assert_eq!(
+            ResponseCache::new(PathBuf::from("/c"), Duration::ZERO, false)
+                .scoped("GHE.example.com:8443", "token")
+                .dir,
+            PathBuf::from("/c/ghe.example.com_8443")
+        );
+
+        std::fs::remove_dir_all(temp_dir("scoped")).unwrap();
+    }
+
+    #[test]
+    fn default_dir_prefers_xdg_cache_home() {
+        with_vars(
+            [("XDG_CACHE_HOME", Some("/xdg")), ("HOME", Some("/home/me"))],
+            || {
+                assert_eq!(
+                    ResponseCache::default_dir(),
+                    Some(PathBuf::from("/xdg/gh-furik"))
+                )
+            },
+        );
+        with_vars(
+            [("XDG_CACHE_HOME", Some("")), ("HOME", Some("/home/me"))],
+            || {
+                assert_eq!(
+                    ResponseCache::default_dir(),
+                    Some(PathBuf::from("/home/me/.cache/gh-furik"))
+                )
+            },
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use super::cache::{hex_digest, host_dir_name};
+use super::graphql::GraphqlResponse;
+use anyhow::Context;
+use std::path::{Path, PathBuf};
+
+const MANIFEST_FILE: &str = "capture.json";
+
+/// GraphQL traffic saved to a directory, so a run can be reproduced without network access.
+///
+/// Each request is stored with its response in a file named after a hash of the request,
+/// under a directory per host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Capture {
+    /// Saves every response fetched from GitHub.
+    Record(PathBuf),
+    /// Serves responses from the directory instead of GitHub.
+    Replay(PathBuf),
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Manifest {
+    today: chrono::NaiveDate,
+}
+
+impl Capture {
+    /// Prepares the capture directory and returns the date the report is run on.
+    ///
+    /// Recording notes `today` in the directory; replaying returns the date noted then, so
+    /// that relative periods and searches up to today send the same requests again.
+    pub fn start(&self, today: chrono::NaiveDate) -> anyhow::Result<chrono::NaiveDate> {
+        match self {
+            Capture::Record(dir) => {
+                std::fs::create_dir_all(dir).with_context(|| {
+                    format!("failed to create capture directory {}", dir.display())
+                })?;
+                let manifest = serde_json::to_string_pretty(&Manifest { today })?;
+                let path = dir.join(MANIFEST_FILE);
+                std::fs::write(&path, manifest)
+                    .with_context(|| format!("failed to write {}", path.display()))?;
+                Ok(today)
+            }
+            Capture::Replay(dir) => {
+                let path = dir.join(MANIFEST_FILE);
+                let manifest = std::fs::read_to_string(&path)
+                    .with_context(|| format!("failed to read {}", path.display()))?;
+                let manifest: Manifest = serde_json::from_str(&manifest)
+                    .with_context(|| format!("failed to parse {}", path.display()))?;
+                Ok(manifest.today)
+            }
+        }
+    }
+
+    pub(crate) fn is_replay(&self) -> bool {
+        matches!(self, Capture::Replay(_))
+    }
+
+    /// The capture for requests sent to `host`.
+    pub(crate) fn scoped(&self, host: &str) -> Self {
+        match self {
+            Capture::Record(dir) => Capture::Record(dir.join(host_dir_name(host))),
+            Capture::Replay(dir) => Capture::Replay(dir.join(host_dir_name(host))),
+        }
+    }
+
+    fn dir(&self) -> &Path {
+        match self {
+            Capture::Record(dir) | Capture::Replay(dir) => dir,
+        }
+    }
+
+    fn path(&self, payload: &serde_json::Value) -> PathBuf {
+        let key = payload.to_string();
+        self.dir()
+            .join(format!("{}.json", hex_digest(key.as_bytes())))
+    }
+
+    /// Loads the recorded response to `payload`.
+    pub(crate) fn load(
+        &self,
+        payload: &serde_json::Value,
+    ) -> anyhow::Result<GraphqlResponse<serde_json::Value>> {
+        let path = self.path(payload);
+        let exchange = std::fs::read_to_string(&path).with_context(|| {
+            format!(
+                "no recorded response in {} for request {payload}",
+                path.display()
+            )
+        })?;
+        let mut exchange: serde_json::Value = serde_json::from_str(&exchange)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        serde_json::from_value(exchange["response"].take())
+            .with_context(|| format!("failed to parse the response in {}", path.display()))
+    }
+
+    /// Saves `payload` and its response when recording.
+    pub(crate) fn save(
+        &self,
+        payload: &serde_json::Value,
+        response: &GraphqlResponse<serde_json::Value>,
+    ) -> anyhow::Result<()> {
+        if self.is_replay() {
+            return Ok(());
+        }
+        let dir = self.dir();
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create capture directory {}", dir.display()))?;
+        let exchange = serde_json::json!({ "request": payload, "response": response });
+        let path = self.path(payload);
+        std::fs::write(&path, serde_json::to_string_pretty(&exchange)?)
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Empty directory for the test `name`, which the test removes when it ends.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "gh-furik-capture-test-{}-{name}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn payload(after: &str) -> serde_json::Value {
+        serde_json::json!({ "query": "query { viewer { login } }", "variables": { "after": after } })
+    }
+
+    #[test]
+    fn replay_serves_recorded_responses() {
+        let dir = temp_dir("roundtrip");
+        let response: GraphqlResponse<serde_json::Value> =
+            serde_json::from_value(serde_json::json!({ "data": { "viewer": { "login": "me" } } }))
+                .unwrap();
+
+        Capture::Record(dir.clone())
+            .scoped("github.com")
+            .save(&payload("a"), &response)
+            .unwrap();
+
+        let replay = Capture::Replay(dir.clone()).scoped("github.com");
+        let replayed = replay.load(&payload("a")).unwrap();
+        assert_eq!(replayed.data.unwrap()["viewer"]["login"], "me");
+
+        let error = replay.load(&payload("b")).unwrap_err();
+        assert!(error.to_string().contains("no recorded response"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn replay_uses_the_recorded_date() {
+        let dir = temp_dir("manifest");
+        let recorded = chrono::NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        let later = chrono::NaiveDate::from_ymd_opt(2025, 6, 1).unwrap();
+
+        assert_eq!(
+            Capture::Record(dir.clone()).start(recorded).unwrap(),
+            recorded
+        );
+        assert_eq!(Capture::Replay(dir.clone()).start(later).unwrap(), recorded);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
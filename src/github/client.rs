@@ -1,35 +1,238 @@
+use super::cache::ResponseCache;
+use super::capture::Capture;
+use super::graphql::GraphqlResponse;
+use super::rate_limit::RateLimitUsage;
+use super::retry::{self, RetryPolicy};
 use anyhow::Context;
+use std::sync::Arc;
+
+/// Settings shared by every client created during a run.
+#[derive(Debug, Clone, Default)]
+pub struct ClientOptions {
+    pub retry: RetryPolicy,
+    /// Where responses are cached, or `None` to always query GitHub.
+    pub cache: Option<ResponseCache>,
+    /// Where traffic is recorded to or replayed from.
+    pub capture: Option<Capture>,
+}
 
 pub struct Client {
     octocrab: octocrab::Octocrab,
-    viewer_login: String,
+    retry: RetryPolicy,
+    cache: Option<ResponseCache>,
+    capture: Option<Capture>,
+    usage: Arc<RateLimitUsage>,
+    login: String,
 }
 
 impl Client {
-    pub async fn new(host: &str) -> anyhow::Result<Self> {
-        let token = fetch_token(host)?;
-        let octocrab = build_github_client(host, token)?;
-        let viewer_login = super::graphql::query_viewer_login(&octocrab).await?;
+    /// Connects to `host` and targets the authenticated user.
+    ///
+    /// Rate-limit points spent by this client and those derived from it are added to `usage`.
+    pub async fn new(
+        host: &str,
+        options: &ClientOptions,
+        usage: Arc<RateLimitUsage>,
+    ) -> anyhow::Result<Self> {
+        let capture = options.capture.as_ref().map(|capture| capture.scoped(host));
+        // Replayed runs never reach GitHub, so they need no token.
+        let token = match &capture {
+            Some(capture) if capture.is_replay() => None,
+            _ => Some(fetch_token(host)?),
+        };
+        let cache = options
+            .cache
+            .as_ref()
+            .zip(token.as_deref())
+            .map(|(cache, token)| cache.scoped(host, token));
+        let mut client = Self {
+            octocrab: build_github_client(host, token)?,
+            retry: options.retry,
+            cache,
+            capture,
+            usage,
+            login: String::new(),
+        };
+        client.login = super::graphql::query_viewer_login(&client).await?;
+        Ok(client)
+    }
+
+    /// Returns a client on the same connection that targets `user` instead.
+    pub async fn for_user(&self, user: &str) -> anyhow::Result<Self> {
+        let login = super::graphql::query_user_login(self, user).await?;
         Ok(Self {
-            octocrab,
-            viewer_login,
+            octocrab: self.octocrab.clone(),
+            retry: self.retry,
+            cache: self.cache.clone(),
+            capture: self.capture.clone(),
+            usage: self.usage.clone(),
+            login,
         })
     }
 
-    pub(crate) fn octocrab(&self) -> &octocrab::Octocrab {
-        &self.octocrab
+    /// Login of the user whose activity is collected.
+    pub(crate) fn login(&self) -> &str {
+        &self.login
+    }
+
+    /// Sends a GraphQL request, retrying server errors and rate limits according to the
+    /// retry policy.
+    ///
+    /// Responses are served from and stored in the cache, when there is one. When
+    /// replaying, responses come from the capture only.
+    pub(crate) async fn graphql(
+        &self,
+        payload: &serde_json::Value,
+    ) -> anyhow::Result<GraphqlResponse<serde_json::Value>> {
+        if let Some(capture) = self.capture.as_ref().filter(|capture| capture.is_replay()) {
+            let response = capture.load(payload)?;
+            self.record_usage(&response);
+            return Ok(response);
+        }
+        if let Some(response) = self.cache.as_ref().and_then(|cache| cache.get(payload)) {
+            self.usage.record_cached();
+            return Ok(response);
+        }
+
+        let mut attempt = 1;
+        loop {
+            let (error, server_wait) = match self.send_graphql(payload).await {
+                Ok(response) => {
+                    self.store(payload, &response);
+                    if let Some(capture) = &self.capture {
+                        capture.save(payload, &response)?;
+                    }
+                    return Ok(response);
+                }
+                Err(Failure::Fatal(error)) => return Err(error),
+                Err(Failure::Transient { error, server_wait }) => (error, server_wait),
+            };
+            let Some(delay) = self.retry.delay(attempt, server_wait) else {
+                return Err(error);
+            };
+            eprintln!(
+                "GraphQL request failed; retrying in {:.1}s attempt={attempt} error={error:#}",
+                delay.as_secs_f64()
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Caches a successful response; responses with errors may be partial and are not kept.
+    fn store(&self, payload: &serde_json::Value, response: &GraphqlResponse<serde_json::Value>) {
+        let Some(cache) = &self.cache else {
+            return;
+        };
+        if response.errors.is_some() {
+            return;
+        }
+        if let Err(error) = cache.put(payload, response) {
+            eprintln!("Warning: {error:#}");
+        }
+    }
+
+    /// Counts a response fetched from GitHub or replayed from a capture.
+    fn record_usage(&self, response: &GraphqlResponse<serde_json::Value>) {
+        self.usage.record(
+            response
+                .data
+                .as_ref()
+                .and_then(|data| data.get("rateLimit")),
+        );
+    }
+
+    async fn send_graphql(
+        &self,
+        payload: &serde_json::Value,
+    ) -> Result<GraphqlResponse<serde_json::Value>, Failure> {
+        let response = self
+            .octocrab
+            ._post("/graphql", Some(payload))
+            .await
+            .map_err(|error| Failure::transient(error.into(), None))?;
+
+        let status = response.status();
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+        };
+        let server_wait = retry::server_wait(
+            header("retry-after"),
+            header("x-ratelimit-remaining"),
+            header("x-ratelimit-reset"),
+            chrono::Utc::now(),
+        );
+        let body = self
+            .octocrab
+            .body_to_string(response)
+            .await
+            .map_err(|error| Failure::transient(error.into(), server_wait))?;
+
+        if !status.is_success() {
+            let error = http_error(status, &body);
+            let rate_limited = server_wait.is_some() || body.contains("rate limit");
+            return Err(
+                if retry::is_retryable_status(status.as_u16(), rate_limited) {
+                    Failure::transient(error, server_wait)
+                } else {
+                    Failure::Fatal(error)
+                },
+            );
+        }
+
+        let response: GraphqlResponse<serde_json::Value> = serde_json::from_str(&body)
+            .context("failed to parse GraphQL response")
+            .map_err(Failure::Fatal)?;
+        self.record_usage(&response);
+        if response.is_rate_limited() {
+            return Err(Failure::transient(
+                anyhow::anyhow!("GraphQL rate limit exceeded"),
+                server_wait,
+            ));
+        }
+        Ok(response)
     }
+}
 
-    pub(crate) fn viewer_login(&self) -> &str {
-        &self.viewer_login
+/// Why a GraphQL request failed, and whether sending it again may help.
+enum Failure {
+    Transient {
+        error: anyhow::Error,
+        server_wait: Option<std::time::Duration>,
+    },
+    Fatal(anyhow::Error),
+}
+
+impl Failure {
+    fn transient(error: anyhow::Error, server_wait: Option<std::time::Duration>) -> Self {
+        Self::Transient { error, server_wait }
     }
 }
 
-fn build_github_client(host: &str, token: String) -> anyhow::Result<octocrab::Octocrab> {
-    let client = octocrab::Octocrab::builder()
+/// Describes an unsuccessful HTTP response, using GitHub's error message when the body has one.
+fn http_error(status: impl std::fmt::Display, body: &str) -> anyhow::Error {
+    let message = serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|body| body.get("message")?.as_str().map(str::to_string));
+    match message {
+        Some(message) => anyhow::anyhow!("GitHub returned HTTP {status}: {message}"),
+        None => anyhow::anyhow!("GitHub returned HTTP {status}"),
+    }
+}
+
+fn build_github_client(host: &str, token: Option<String>) -> anyhow::Result<octocrab::Octocrab> {
+    let mut builder = octocrab::Octocrab::builder()
         .base_uri(api_base_url(host))
-        .context("failed to set base URI")?
-        .personal_token(token)
+        .context("failed to set base URI")?;
+    if let Some(token) = token {
+        builder = builder.personal_token(token);
+    }
+    let client = builder
+        // Retries are handled by `Client::graphql`, which knows about rate limits.
+        .add_retry_config(octocrab::service::middleware::retry::RetryConfig::None)
         .build()?;
     Ok(client)
 }
@@ -93,7 +296,7 @@ fn token_from_gh(host: &str) -> anyhow::Result<Option<String>> {
 
 #[cfg(test)]
 mod tests {
-    use super::fetch_token;
+    use super::{fetch_token, http_error};
     use temp_env::with_vars;
 
     #[test]
@@ -154,4 +357,19 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn http_error_uses_github_message_when_present() {
+        let error = http_error(
+            "403 Forbidden",
+            r#"{"message": "You have exceeded a secondary rate limit."}"#,
+        );
+        assert_eq!(
+            error.to_string(),
+            "GitHub returned HTTP 403 Forbidden: You have exceeded a secondary rate limit."
+        );
+
+        let error = http_error("502 Bad Gateway", "<html>Bad Gateway</html>");
+        assert_eq!(error.to_string(), "GitHub returned HTTP 502 Bad Gateway");
+    }
 }
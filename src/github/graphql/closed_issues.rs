@@ -1,20 +1,50 @@
-use super::fetch::{event_items_from_search_node, fetch_search_nodes_range};
-use super::types::{EventItem, EventKind};
+use super::error::skip_bad_node;
+use super::fetch::{
+    SearchField, event_items_from_search_node, fetch_search_nodes_range, plan_search_range,
+};
+use super::types::{EventItem, EventKind, PlannedSearch};
+use crate::range::DateRange;
 
+/// Issues closed within the range, along with reopen events of the same issues.
 pub(crate) async fn query_closed_issues(
     client: &crate::github::Client,
-    from: chrono::NaiveDate,
-    to: chrono::NaiveDate,
+    range: DateRange,
+    search_filter: &str,
 ) -> anyhow::Result<Vec<EventItem>> {
-    if from > to {
+    if range.from > range.to {
         return Ok(Vec::new());
     }
 
-    let nodes = fetch_search_nodes_range(client.octocrab(), "is:issue", from, to).await?;
+    let nodes = fetch_search_nodes_range(
+        client,
+        "is:issue",
+        SearchField::Closed,
+        search_filter,
+        range,
+    )
+    .await?;
 
     Ok(nodes
         .into_iter()
-        .flat_map(|node| event_items_from_search_node(&node, client.viewer_login(), from, to))
-        .filter(|item| matches!(item.kind, EventKind::IssueClosed))
+        .flat_map(|node| {
+            skip_bad_node(event_items_from_search_node(&node, client.login(), range))
+                .unwrap_or_default()
+        })
+        .filter(|item| matches!(item.kind, EventKind::IssueClosed | EventKind::IssueReopened))
         .collect())
 }
+
+pub(crate) async fn plan_closed_issues(
+    client: &crate::github::Client,
+    range: DateRange,
+    search_filter: &str,
+) -> anyhow::Result<Vec<PlannedSearch>> {
+    plan_search_range(
+        client,
+        "is:issue",
+        SearchField::Closed,
+        search_filter,
+        range,
+    )
+    .await
+}
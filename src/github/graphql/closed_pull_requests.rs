@@ -1,25 +1,38 @@
-use super::fetch::{event_items_from_search_node, fetch_search_nodes_range};
-use super::types::{EventItem, EventKind};
+use super::error::skip_bad_node;
+use super::fetch::{
+    SearchField, event_items_from_search_node, fetch_search_nodes_range, plan_search_range,
+};
+use super::types::{EventItem, EventKind, PlannedSearch};
+use crate::range::DateRange;
 use std::collections::HashMap;
 
+/// Pull requests closed or merged within the range, along with reopen events of the same
+/// pull requests.
 pub(crate) async fn query_closed_pull_requests(
     client: &crate::github::Client,
-    from: chrono::NaiveDate,
-    to: chrono::NaiveDate,
+    range: DateRange,
+    search_filter: &str,
 ) -> anyhow::Result<Vec<EventItem>> {
-    if from > to {
+    if range.from > range.to {
         return Ok(Vec::new());
     }
 
-    let nodes = fetch_search_nodes_range(client.octocrab(), "is:pr", from, to).await?;
+    let nodes =
+        fetch_search_nodes_range(client, "is:pr", SearchField::Closed, search_filter, range)
+            .await?;
 
     let items: Vec<_> = nodes
         .into_iter()
-        .flat_map(|node| event_items_from_search_node(&node, client.viewer_login(), from, to))
+        .flat_map(|node| {
+            skip_bad_node(event_items_from_search_node(&node, client.login(), range))
+                .unwrap_or_default()
+        })
         .filter(|item| {
             matches!(
                 item.kind,
-                EventKind::PullRequestClosed | EventKind::PullRequestMerged
+                EventKind::PullRequestClosed
+                    | EventKind::PullRequestMerged
+                    | EventKind::PullRequestReopened
             )
         })
         .collect();
@@ -27,6 +40,14 @@ pub(crate) async fn query_closed_pull_requests(
     Ok(filter_out_closed_when_merged(items))
 }
 
+pub(crate) async fn plan_closed_pull_requests(
+    client: &crate::github::Client,
+    range: DateRange,
+    search_filter: &str,
+) -> anyhow::Result<Vec<PlannedSearch>> {
+    plan_search_range(client, "is:pr", SearchField::Closed, search_filter, range).await
+}
+
 fn filter_out_closed_when_merged(items: Vec<EventItem>) -> Vec<EventItem> {
     let mut merged_counts: HashMap<(String, chrono::DateTime<chrono::Utc>), usize> = HashMap::new();
     for item in items
@@ -70,6 +91,8 @@ mod tests {
             repository: "owner/repo".to_string(),
             subject_title: "Sample PR".to_string(),
             subject_url: subject_url.to_string(),
+            diff_stat: None,
+            review_state: None,
         }
     }
 
@@ -117,6 +140,8 @@ mod tests {
                 repository: "owner/repo".to_string(),
                 subject_title: "Sample PR".to_string(),
                 subject_url: "https://example.test/pull/1".to_string(),
+                diff_stat: None,
+                review_state: None,
             },
             EventItem {
                 kind: EventKind::PullRequestClosed,
@@ -126,6 +151,8 @@ mod tests {
                 repository: "owner/repo".to_string(),
                 subject_title: "Sample PR".to_string(),
                 subject_url: "https://example.test/pull/1".to_string(),
+                diff_stat: None,
+                review_state: None,
             },
             EventItem {
                 kind: EventKind::PullRequestMerged,
@@ -135,6 +162,8 @@ mod tests {
                 repository: "owner/repo".to_string(),
                 subject_title: "Sample PR".to_string(),
                 subject_url: "https://example.test/pull/1".to_string(),
+                diff_stat: None,
+                review_state: None,
             },
         ];
 
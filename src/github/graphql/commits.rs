@@ -0,0 +1,276 @@
+use super::error::{NodeContext, Required, ShapeError, skip_bad_node};
+use super::fetch::{MAX_PAGES, contribution_ranges, graphql_data, in_range};
+use super::queries::{COMMIT_CONTRIBUTIONS_QUERY, COMMIT_HISTORY_QUERY};
+use super::types::*;
+use crate::filter::RepoFilter;
+use crate::range::DateRange;
+use anyhow::Context;
+use futures::stream::{StreamExt, TryStreamExt};
+use valq::query_value;
+
+/// Most repositories `commitContributionsByRepository` returns; the API cannot page past it.
+const MAX_COMMIT_REPOSITORIES: usize = 100;
+/// Repositories whose commit history is fetched at the same time.
+const HISTORY_CONCURRENCY: usize = 8;
+
+/// Repository the user committed to, as reported by `commitContributionsByRepository`.
+#[derive(Debug, PartialEq, Eq)]
+struct CommitRepository {
+    owner: String,
+    name: String,
+    name_with_owner: String,
+    url: String,
+}
+
+/// Commits authored by the user on the default branch of each repository they contributed to.
+///
+/// Commits on other branches are not counted, matching GitHub's contribution graph. At most
+/// 100 repositories are reported per year of the range; a warning is printed when the
+/// limit is reached. Only repositories selected by `repos` have their history fetched.
+pub(crate) async fn query_commits(
+    client: &crate::github::Client,
+    range: DateRange,
+    repos: &RepoFilter,
+) -> anyhow::Result<Vec<EventItem>> {
+    let mut author_id = None;
+    let mut repositories: Vec<CommitRepository> = Vec::new();
+    for chunk in contribution_ranges(range) {
+        let (id, chunk_repositories) = query_commit_repositories(client, chunk).await?;
+        author_id = Some(id);
+        add_repositories(&mut repositories, chunk_repositories, repos);
+    }
+    let Some(author_id) = author_id else {
+        return Ok(Vec::new());
+    };
+
+    let author_id = author_id.as_str();
+    let histories = repositories
+        .iter()
+        .map(|repository| query_commit_history(client, repository, author_id, range));
+    let histories: Vec<Vec<EventItem>> = futures::stream::iter(histories.collect::<Vec<_>>())
+        .buffer_unordered(HISTORY_CONCURRENCY)
+        .try_collect()
+        .await?;
+    Ok(histories.into_iter().flatten().collect())
+}
+
+/// Adds the repositories `repos` selects that are not in `repositories` yet.
+fn add_repositories(
+    repositories: &mut Vec<CommitRepository>,
+    chunk: Vec<CommitRepository>,
+    repos: &RepoFilter,
+) {
+    for repository in chunk {
+        if repos.matches(&repository.name_with_owner) && !repositories.contains(&repository) {
+            repositories.push(repository);
+        }
+    }
+}
+
+async fn query_commit_repositories(
+    client: &crate::github::Client,
+    range: DateRange,
+) -> anyhow::Result<(String, Vec<CommitRepository>)> {
+    let payload = serde_json::json!({
+        "query": COMMIT_CONTRIBUTIONS_QUERY,
+        "variables": {
+            "login": client.login(),
+            "from": range.start(),
+            "to": range.end(),
+            "maxRepositories": MAX_COMMIT_REPOSITORIES,
+        },
+    });
+
+    let resp = client
+        .graphql(&payload)
+        .await
+        .context("GraphQL commit contributions query failed")?;
+
+    let data = graphql_data(resp)?;
+    let context = NodeContext::response("commit contributions");
+    let user = query_value!(data.user).required(&context, "user")?;
+    let id = query_value!(user.id -> str).required(&context, "user.id")?;
+    let contributions =
+        query_value!(user.contributionsCollection.commitContributionsByRepository -> array)
+            .required(
+                &context,
+                "user.contributionsCollection.commitContributionsByRepository",
+            )?;
+
+    if contributions.len() >= MAX_COMMIT_REPOSITORIES {
+        eprintln!(
+            "Warning: {} committed to {MAX_COMMIT_REPOSITORIES} or more repositories between {} and {}; \
+             commits in repositories past the first {MAX_COMMIT_REPOSITORIES} are not reported",
+            client.login(),
+            range.from,
+            range.to
+        );
+    }
+    let repositories = contributions
+        .iter()
+        .filter_map(|contribution| skip_bad_node(commit_repository_from_node(contribution)))
+        .collect();
+
+    Ok((id.to_string(), repositories))
+}
+
+fn commit_repository_from_node(
+    contribution: &serde_json::Value,
+) -> Result<CommitRepository, ShapeError> {
+    let context = NodeContext::response("commit contributions");
+    let repository = query_value!(contribution.repository).required(&context, "repository")?;
+    let context = NodeContext::new("commit contributions", repository);
+    Ok(CommitRepository {
+        owner: query_value!(repository.owner.login -> str)
+            .required(&context, "repository.owner.login")?
+            .to_string(),
+        name: query_value!(repository.name -> str)
+            .required(&context, "repository.name")?
+            .to_string(),
+        name_with_owner: query_value!(repository["nameWithOwner"] -> str)
+            .required(&context, "repository.nameWithOwner")?
+            .to_string(),
+        url: query_value!(repository.url -> str)
+            .required(&context, "repository.url")?
+            .to_string(),
+    })
+}
+
+async fn query_commit_history(
+    client: &crate::github::Client,
+    repository: &CommitRepository,
+    author_id: &str,
+    range: DateRange,
+) -> anyhow::Result<Vec<EventItem>> {
+    let subject_url = format!("{}/commits?author={}", repository.url, client.login());
+    let mut after: Option<String> = None;
+    let mut out = Vec::new();
+
+    for _ in 0..MAX_PAGES {
+        let payload = serde_json::json!({
+            "query": COMMIT_HISTORY_QUERY,
+            "variables": {
+                "owner": repository.owner,
+                "name": repository.name,
+                "author": author_id,
+                "since": range.start(),
+                "until": range.end(),
+                "after": after.clone(),
+            },
+        });
+
+        let resp = client.graphql(&payload).await.with_context(|| {
+            format!(
+                "GraphQL commit history query failed for {}",
+                repository.name_with_owner
+            )
+        })?;
+
+        let data = graphql_data(resp)?;
+        // Empty repositories have no default branch.
+        let Some(history) = query_value!(data.repository.defaultBranchRef.target.history) else {
+            break;
+        };
+
+        if let Some(nodes) = query_value!(history.nodes -> array) {
+            out.extend(
+                nodes
+                    .iter()
+                    .filter(|node| !node.is_null())
+                    .filter_map(|node| {
+                        skip_bad_node(commit_event_from_node(
+                            node,
+                            repository,
+                            &subject_url,
+                            range,
+                        ))
+                        .flatten()
+                    }),
+            );
+        }
+
+        let has_next_page = query_value!(history.pageInfo.hasNextPage -> bool).required(
+            &NodeContext::response("commit history"),
+            "repository.defaultBranchRef.target.history.pageInfo.hasNextPage",
+        )?;
+        if !has_next_page {
+            break;
+        }
+        after = query_value!(history.pageInfo.endCursor -> str).map(str::to_string);
+        if after.is_none() {
+            break;
+        }
+    }
+
+    Ok(out)
+}
+
+fn commit_event_from_node(
+    node: &serde_json::Value,
+    repository: &CommitRepository,
+    subject_url: &str,
+    range: DateRange,
+) -> Result<Option<EventItem>, ShapeError> {
+    let context = NodeContext::new("commit history", node);
+    let committed_at =
+        context.datetime(query_value!(node["committedDate"] -> str), "committedDate")?;
+    if !in_range(committed_at, range) {
+        return Ok(None);
+    }
+    Ok(Some(EventItem {
+        kind: EventKind::Commit,
+        created_at: committed_at,
+        url: query_value!(node.url -> str)
+            .required(&context, "url")?
+            .to_string(),
+        body: query_value!(node["messageHeadline"] -> str).map(str::to_string),
+        repository: repository.name_with_owner.clone(),
+        subject_title: "Commits".to_string(),
+        subject_url: subject_url.to_string(),
+        diff_stat: Some(DiffStat {
+            additions: query_value!(node.additions -> u64).required(&context, "additions")?,
+            deletions: query_value!(node.deletions -> u64).required(&context, "deletions")?,
+        }),
+        review_state: None,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repository(name_with_owner: &str) -> CommitRepository {
+        let (owner, name) = name_with_owner.split_once('/').unwrap();
+        CommitRepository {
+            owner: owner.to_string(),
+            name: name.to_string(),
+            name_with_owner: name_with_owner.to_string(),
+            url: format!("https://example.test/{name_with_owner}"),
+        }
+    }
+
+    #[test]
+    fn add_repositories_skips_filtered_and_known_repositories() {
+        let repos = RepoFilter::new(
+            vec![],
+            vec!["myorg/legacy".to_string()],
+            vec!["myorg".to_string()],
+        );
+        let mut repositories = vec![repository("myorg/app")];
+        add_repositories(
+            &mut repositories,
+            vec![
+                repository("myorg/app"),
+                repository("myorg/legacy"),
+                repository("other/lib"),
+                repository("myorg/docs"),
+            ],
+            &repos,
+        );
+
+        assert_eq!(
+            repositories,
+            [repository("myorg/app"), repository("myorg/docs")]
+        );
+    }
+}
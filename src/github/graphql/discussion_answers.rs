@@ -0,0 +1,109 @@
+use super::error::{NodeContext, Required, ShapeError};
+use super::fetch::{fetch_paginated_json, in_range};
+use super::queries::QueryKind;
+use super::types::*;
+use crate::range::DateRange;
+use valq::query_value;
+
+/// Comments by the user that were marked as the answer to a discussion within the range.
+///
+/// An old comment can be marked as the answer at any time, so every answer the user has
+/// written is read, however short the range is.
+pub(crate) async fn query_discussion_answers(
+    client: &crate::github::Client,
+    range: DateRange,
+) -> anyhow::Result<Vec<EventItem>> {
+    fetch_paginated_json(
+        client,
+        QueryKind::DiscussionComments { only_answers: true },
+        move |node| discussion_answer_event_from_node(node, range),
+        |_| Ok(false),
+    )
+    .await
+}
+
+fn discussion_answer_event_from_node(
+    node: &serde_json::Value,
+    range: DateRange,
+) -> Result<Option<EventItem>, ShapeError> {
+    let context = NodeContext::new("discussion answers", node);
+    // The answer may have been unmarked since, in which case there is no timestamp.
+    let Some(chosen_at) = query_value!(node.discussion["answerChosenAt"] -> str) else {
+        return Ok(None);
+    };
+    let chosen_at = context.datetime(Some(chosen_at), "discussion.answerChosenAt")?;
+    if !in_range(chosen_at, range) {
+        return Ok(None);
+    }
+    let url = query_value!(node.url -> str).required(&context, "url")?;
+    let title =
+        query_value!(node.discussion.title -> str).required(&context, "discussion.title")?;
+    let subject_url =
+        query_value!(node.discussion.url -> str).required(&context, "discussion.url")?;
+    let repository = query_value!(node.discussion.repository["nameWithOwner"] -> str)
+        .required(&context, "discussion.repository.nameWithOwner")?;
+
+    Ok(Some(EventItem {
+        kind: EventKind::DiscussionAnswerMarked,
+        created_at: chosen_at,
+        url: url.to_string(),
+        body: None,
+        repository: repository.to_string(),
+        subject_title: title.to_string(),
+        subject_url: subject_url.to_string(),
+        diff_stat: None,
+        review_state: None,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range() -> DateRange {
+        DateRange {
+            from: chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            to: chrono::NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+            zone: crate::range::Zone::UTC,
+        }
+    }
+
+    fn node(answer_chosen_at: Option<&str>) -> serde_json::Value {
+        serde_json::json!({
+            "createdAt": "2024-12-20T00:00:00Z",
+            "url": "https://example.test/discussions/1#discussioncomment-1",
+            "body": "Use the v2 API.",
+            "discussion": {
+                "title": "RFC: API v2",
+                "url": "https://example.test/discussions/1",
+                "answerChosenAt": answer_chosen_at,
+                "repository": { "nameWithOwner": "o/rfcs" }
+            }
+        })
+    }
+
+    #[test]
+    fn answer_event_uses_answer_chosen_at() {
+        let event = discussion_answer_event_from_node(&node(Some("2025-01-05T00:00:00Z")), range())
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(event.kind, EventKind::DiscussionAnswerMarked);
+        assert_eq!(event.created_at.to_rfc3339(), "2025-01-05T00:00:00+00:00");
+        assert_eq!(event.subject_url, "https://example.test/discussions/1");
+    }
+
+    #[test]
+    fn answer_event_skips_unmarked_or_out_of_range_answers() {
+        assert!(
+            discussion_answer_event_from_node(&node(None), range())
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            discussion_answer_event_from_node(&node(Some("2025-02-01T00:00:00Z")), range())
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use super::error::{NodeContext, Required, ShapeError};
+use super::fetch::{fetch_paginated_json, in_range};
+use super::queries::QueryKind;
+use super::types::*;
+use crate::range::DateRange;
+use valq::query_value;
+
+pub(crate) async fn query_discussion_comments(
+    client: &crate::github::Client,
+    range: DateRange,
+) -> anyhow::Result<Vec<EventItem>> {
+    // Comments are paged from the newest, so paging stops at the first one before the range.
+    fetch_paginated_json(
+        client,
+        QueryKind::DiscussionComments {
+            only_answers: false,
+        },
+        move |node| discussion_comment_event_from_node(node, range),
+        move |node| {
+            let created_at = NodeContext::new("discussion comments", node)
+                .datetime(query_value!(node["createdAt"] -> str), "createdAt")?;
+            Ok(range.zone.date_of(created_at) < range.from)
+        },
+    )
+    .await
+}
+
+fn discussion_comment_event_from_node(
+    node: &serde_json::Value,
+    range: DateRange,
+) -> Result<Option<EventItem>, ShapeError> {
+    let context = NodeContext::new("discussion comments", node);
+    let created_at = context.datetime(query_value!(node["createdAt"] -> str), "createdAt")?;
+    if !in_range(created_at, range) {
+        return Ok(None);
+    }
+    let url = query_value!(node.url -> str).required(&context, "url")?;
+    let body = query_value!(node.body -> str).required(&context, "body")?;
+    let title =
+        query_value!(node.discussion.title -> str).required(&context, "discussion.title")?;
+    let subject_url =
+        query_value!(node.discussion.url -> str).required(&context, "discussion.url")?;
+    let repository = query_value!(node.discussion.repository["nameWithOwner"] -> str)
+        .required(&context, "discussion.repository.nameWithOwner")?;
+
+    Ok(Some(EventItem {
+        kind: EventKind::DiscussionComment,
+        created_at,
+        url: url.to_string(),
+        body: Some(body.to_string()),
+        repository: repository.to_string(),
+        subject_title: title.to_string(),
+        subject_url: subject_url.to_string(),
+        diff_stat: None,
+        review_state: None,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range() -> DateRange {
+        DateRange {
+            from: chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            to: chrono::NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+            zone: crate::range::Zone::UTC,
+        }
+    }
+
+    fn node(created_at: &str) -> serde_json::Value {
+        serde_json::json!({
+            "createdAt": created_at,
+            "url": "https://example.test/discussions/1#discussioncomment-1",
+            "body": "Use the v2 API.",
+            "discussion": {
+                "title": "RFC: API v2",
+                "url": "https://example.test/discussions/1",
+                "answerChosenAt": null,
+                "repository": { "nameWithOwner": "o/rfcs" }
+            }
+        })
+    }
+
+    #[test]
+    fn comment_event_links_the_discussion() {
+        let event = discussion_comment_event_from_node(&node("2025-01-05T00:00:00Z"), range())
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(event.kind, EventKind::DiscussionComment);
+        assert_eq!(
+            event.url,
+            "https://example.test/discussions/1#discussioncomment-1"
+        );
+        assert_eq!(event.body.as_deref(), Some("Use the v2 API."));
+        assert_eq!(event.repository, "o/rfcs");
+        assert_eq!(event.subject_title, "RFC: API v2");
+        assert_eq!(event.subject_url, "https://example.test/discussions/1");
+    }
+
+    #[test]
+    fn comment_event_skips_comments_outside_the_range() {
+        for created_at in ["2024-12-31T23:59:59Z", "2025-02-01T00:00:00Z"] {
+            assert!(
+                discussion_comment_event_from_node(&node(created_at), range())
+                    .unwrap()
+                    .is_none()
+            );
+        }
+    }
+
+    #[test]
+    fn comment_event_requires_the_discussion() {
+        let mut node = node("2025-01-05T00:00:00Z");
+        node["discussion"] = serde_json::Value::Null;
+        assert!(discussion_comment_event_from_node(&node, range()).is_err());
+    }
+}
@@ -0,0 +1,155 @@
+use std::fmt;
+
+/// A GraphQL response that does not have the shape a query expects.
+///
+/// Raised instead of panicking when a field is missing or null, e.g. on older GHES
+/// versions or for nodes whose repository has been deleted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShapeError {
+    pub query: &'static str,
+    pub node_url: Option<String>,
+    pub field: &'static str,
+    pub problem: Problem,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Problem {
+    Missing,
+    Invalid,
+}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let problem = match self.problem {
+            Problem::Missing => "missing",
+            Problem::Invalid => "invalid",
+        };
+        write!(
+            f,
+            "{} response has {problem} field `{}`",
+            self.query, self.field
+        )?;
+        if let Some(url) = &self.node_url {
+            write!(f, " in node {url}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ShapeError {}
+
+/// Where fields are read from, so that a [`ShapeError`] can name the query and node.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct NodeContext<'a> {
+    query: &'static str,
+    node_url: Option<&'a str>,
+}
+
+impl<'a> NodeContext<'a> {
+    /// Context for a node; its `url` field, when present, identifies it in errors.
+    pub(super) fn new(query: &'static str, node: &'a serde_json::Value) -> Self {
+        Self {
+            query,
+            node_url: node.get("url").and_then(|url| url.as_str()),
+        }
+    }
+
+    /// Context for the top level of a response.
+    pub(super) fn response(query: &'static str) -> NodeContext<'static> {
+        NodeContext {
+            query,
+            node_url: None,
+        }
+    }
+
+    pub(super) fn error(&self, field: &'static str, problem: Problem) -> ShapeError {
+        ShapeError {
+            query: self.query,
+            node_url: self.node_url.map(str::to_string),
+            field,
+            problem,
+        }
+    }
+
+    /// Parses a required RFC 3339 timestamp field.
+    pub(super) fn datetime(
+        &self,
+        value: Option<&str>,
+        field: &'static str,
+    ) -> Result<chrono::DateTime<chrono::Utc>, ShapeError> {
+        let value = value.required(self, field)?;
+        chrono::DateTime::parse_from_rfc3339(value)
+            .map(|dt| dt.with_timezone(&chrono::Utc))
+            .map_err(|_| self.error(field, Problem::Invalid))
+    }
+}
+
+pub(super) trait Required<T> {
+    fn required(self, context: &NodeContext<'_>, field: &'static str) -> Result<T, ShapeError>;
+}
+
+impl<T> Required<T> for Option<T> {
+    fn required(self, context: &NodeContext<'_>, field: &'static str) -> Result<T, ShapeError> {
+        self.ok_or_else(|| context.error(field, Problem::Missing))
+    }
+}
+
+/// Reports a node that could not be parsed and drops it, so one bad node does not abort a query.
+pub(super) fn skip_bad_node<T>(result: Result<T, ShapeError>) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(error) => {
+            eprintln!("Warning: skipping node: {error}");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shape_error_names_query_node_and_field() {
+        let node = serde_json::json!({ "url": "https://example.test/issues/1" });
+        let context = NodeContext::new("opened issues", &node);
+
+        let error = None::<&str>
+            .required(&context, "repository.nameWithOwner")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "opened issues response has missing field `repository.nameWithOwner` \
+             in node https://example.test/issues/1"
+        );
+    }
+
+    #[test]
+    fn datetime_reports_invalid_values() {
+        let context = NodeContext::response("viewer");
+
+        assert_eq!(
+            context
+                .datetime(Some("yesterday"), "createdAt")
+                .unwrap_err(),
+            ShapeError {
+                query: "viewer",
+                node_url: None,
+                field: "createdAt",
+                problem: Problem::Invalid,
+            }
+        );
+        assert!(
+            context
+                .datetime(Some("2025-01-01T00:00:00Z"), "createdAt")
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn skip_bad_node_drops_errors() {
+        let context = NodeContext::response("search");
+        assert_eq!(skip_bad_node(Ok::<_, ShapeError>(1)), Some(1));
+        assert_eq!(skip_bad_node(None::<i32>.required(&context, "title")), None);
+    }
+}
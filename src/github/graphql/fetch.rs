@@ -1,45 +1,53 @@
 use anyhow::Context;
-use chrono::TimeZone;
-use std::time::Duration;
 
 use valq::query_value;
 
-use super::queries::{QueryKind, SEARCH_COUNT_QUERY, SEARCH_QUERY};
+use super::error::{NodeContext, Required, ShapeError, skip_bad_node};
+use super::queries::{QueryKind, SEARCH_COUNT_QUERY, SEARCH_QUERY, TIMELINE_ITEMS_QUERY};
 use super::types::*;
+use crate::range::DateRange;
 
 pub(super) const MAX_PAGES: usize = 1000;
 const SEARCH_LIMIT: i32 = 1000;
-const SEARCH_RETRIES: usize = 3;
+/// Nodes per page of `search.graphql`.
+pub(super) const SEARCH_PAGE_SIZE: u64 = 50;
+const SEARCH_DATETIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S+00:00";
 
 pub(super) fn event_items_from_search_node(
     node: &serde_json::Value,
-    viewer_login: &str,
-    from: chrono::NaiveDate,
-    to: chrono::NaiveDate,
-) -> Vec<EventItem> {
+    login: &str,
+    range: DateRange,
+) -> Result<Vec<EventItem>, ShapeError> {
     let mut items = Vec::new();
 
-    let typename = query_value!(node["__typename"] -> str).expect("search node missing __typename");
-    let url = query_value!(node.url -> str).expect("search node missing url");
-    let title = query_value!(node.title -> str).expect("search node missing title");
+    let context = NodeContext::new("search", node);
+    let typename = query_value!(node["__typename"] -> str).required(&context, "__typename")?;
+    let url = query_value!(node.url -> str).required(&context, "url")?;
+    let title = query_value!(node.title -> str).required(&context, "title")?;
     let repository = query_value!(node.repository["nameWithOwner"] -> str)
-        .expect("search node missing repository nameWithOwner");
+        .required(&context, "repository.nameWithOwner")?;
 
     let timeline_nodes = query_value!(node.timelineItems.nodes -> array);
 
     if let Some(nodes) = timeline_nodes {
         for node in nodes.iter().filter(|node| !node.is_null()) {
-            let event_type =
-                query_value!(node["__typename"] -> str).expect("timeline node missing __typename");
+            let Some(event_type) = skip_bad_node(
+                query_value!(node["__typename"] -> str)
+                    .required(&context, "timelineItems.nodes.__typename"),
+            ) else {
+                continue;
+            };
             let actor_login = query_value!(node.actor.login -> str);
-            if !actor_matches(actor_login, viewer_login) {
+            if !actor_matches(actor_login, login) {
                 continue;
             }
-            let created_at = parse_datetime(
-                query_value!(node["createdAt"] -> str).expect("timeline node missing createdAt"),
-            )
-            .expect("timeline node invalid createdAt");
-            if !in_range(created_at, from, to) {
+            let Some(created_at) = skip_bad_node(context.datetime(
+                query_value!(node["createdAt"] -> str),
+                "timelineItems.nodes.createdAt",
+            )) else {
+                continue;
+            };
+            if !in_range(created_at, range) {
                 continue;
             }
 
@@ -58,6 +66,26 @@ pub(super) fn event_items_from_search_node(
                         repository: repository.to_string(),
                         subject_title: title.to_string(),
                         subject_url: url.to_string(),
+                        diff_stat: None,
+                        review_state: None,
+                    });
+                }
+                "ReopenedEvent" => {
+                    let kind = if typename == "Issue" {
+                        EventKind::IssueReopened
+                    } else {
+                        EventKind::PullRequestReopened
+                    };
+                    items.push(EventItem {
+                        kind,
+                        created_at,
+                        url: url.to_string(),
+                        body: None,
+                        repository: repository.to_string(),
+                        subject_title: title.to_string(),
+                        subject_url: url.to_string(),
+                        diff_stat: None,
+                        review_state: None,
                     });
                 }
                 "MergedEvent" if typename == "PullRequest" => {
@@ -69,6 +97,8 @@ pub(super) fn event_items_from_search_node(
                         repository: repository.to_string(),
                         subject_title: title.to_string(),
                         subject_url: url.to_string(),
+                        diff_stat: None,
+                        review_state: None,
                     });
                 }
                 _ => {}
@@ -76,125 +106,210 @@ pub(super) fn event_items_from_search_node(
         }
     }
 
-    items
+    Ok(items)
 }
 
-fn actor_matches(actor_login: Option<&str>, viewer_login: &str) -> bool {
+fn actor_matches(actor_login: Option<&str>, login: &str) -> bool {
     actor_login
-        .map(|actor_login| actor_login == viewer_login)
+        .map(|actor_login| actor_login == login)
         .unwrap_or(false)
 }
 
+/// Timestamp qualifier a search range is applied to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SearchField {
+    Closed,
+    Updated,
+}
+
+impl SearchField {
+    fn qualifier(self) -> &'static str {
+        match self {
+            SearchField::Closed => "closed",
+            SearchField::Updated => "updated",
+        }
+    }
+}
+
 pub(super) async fn fetch_search_nodes_range(
-    client: &octocrab::Octocrab,
+    client: &crate::github::Client,
     query_base: &str,
-    from: chrono::NaiveDate,
-    to: chrono::NaiveDate,
+    field: SearchField,
+    search_filter: &str,
+    range: DateRange,
 ) -> anyhow::Result<Vec<serde_json::Value>> {
-    let ranges = split_ranges_by_count(client, query_base, from, to).await?;
+    let query_base = search_base(client, query_base, search_filter);
+    let since = range.start();
+    let windows = split_ranges_by_count(client, &query_base, field, range).await?;
     let mut out = Vec::new();
-    for (start, end) in ranges {
-        let query = search_query(query_base, start, end);
-        out.extend(fetch_search_nodes(client, &query).await?);
+    for (window, _) in windows {
+        let query = search_query(&query_base, field, window);
+        for mut node in fetch_search_nodes(client, &query, since).await? {
+            complete_timeline(client, &mut node, since).await?;
+            out.push(node);
+        }
     }
     Ok(out)
 }
 
+/// Lists the searches `fetch_search_nodes_range` would run, without fetching any nodes.
+pub(super) async fn plan_search_range(
+    client: &crate::github::Client,
+    query_base: &str,
+    field: SearchField,
+    search_filter: &str,
+    range: DateRange,
+) -> anyhow::Result<Vec<PlannedSearch>> {
+    let query_base = search_base(client, query_base, search_filter);
+    let windows = split_ranges_by_count(client, &query_base, field, range).await?;
+    Ok(windows
+        .into_iter()
+        .map(|(window, count)| PlannedSearch {
+            query: search_query(&query_base, field, window),
+            count: count.max(0) as u64,
+        })
+        .collect())
+}
+
+fn search_base(client: &crate::github::Client, query_base: &str, search_filter: &str) -> String {
+    let involves = format!("involves:{}", client.login());
+    [query_base, &involves, search_filter]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Inclusive UTC interval that a search qualifier is applied to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SearchWindow {
+    start: chrono::DateTime<chrono::Utc>,
+    end: chrono::DateTime<chrono::Utc>,
+}
+
+impl SearchWindow {
+    fn from_range(range: DateRange) -> Self {
+        Self {
+            start: range.start(),
+            end: range.end(),
+        }
+    }
+
+    /// Splits the window into two halves at whole-second boundaries.
+    ///
+    /// Returns `None` once the window is a single second and cannot be narrowed further.
+    fn split(self) -> Option<(Self, Self)> {
+        let seconds = (self.end - self.start).num_seconds();
+        if seconds < 1 {
+            return None;
+        }
+        let mid = self.start + chrono::Duration::seconds(seconds / 2);
+        Some((
+            Self {
+                start: self.start,
+                end: mid,
+            },
+            Self {
+                start: mid + chrono::Duration::seconds(1),
+                end: self.end,
+            },
+        ))
+    }
+}
+
+/// Narrows the range until every window has at most `SEARCH_LIMIT` results, returning
+/// each window with its result count.
+///
+/// Busy days are split further by time of day, down to single seconds. A single second
+/// that still has more results cannot be narrowed; a warning names the results lost.
 async fn split_ranges_by_count(
-    client: &octocrab::Octocrab,
+    client: &crate::github::Client,
     query_base: &str,
-    from: chrono::NaiveDate,
-    to: chrono::NaiveDate,
-) -> anyhow::Result<Vec<(chrono::NaiveDate, chrono::NaiveDate)>> {
-    let mut pending = Vec::new();
-    pending.push((from, to));
+    field: SearchField,
+    range: DateRange,
+) -> anyhow::Result<Vec<(SearchWindow, i32)>> {
+    let windows = split_windows_by_count(range, |window| {
+        let query = search_query(query_base, field, window);
+        async move { fetch_search_count(client, &query).await }
+    })
+    .await?;
 
-    let mut out = Vec::new();
-    while let Some((start, end)) = pending.pop() {
-        if start > end {
-            continue;
+    for &(window, count) in &windows {
+        if let Some(warning) =
+            truncated_search_warning(&search_query(query_base, field, window), count)
+        {
+            eprintln!("{warning}");
         }
+    }
+    Ok(windows)
+}
+
+async fn split_windows_by_count<F, Fut>(
+    range: DateRange,
+    mut count_results: F,
+) -> anyhow::Result<Vec<(SearchWindow, i32)>>
+where
+    F: FnMut(SearchWindow) -> Fut,
+    Fut: std::future::Future<Output = anyhow::Result<i32>>,
+{
+    let mut pending = Vec::new();
+    if range.from <= range.to {
+        pending.push(SearchWindow::from_range(range));
+    }
 
-        let query = search_query(query_base, start, end);
-        let count = fetch_search_count(client, &query).await?;
+    let mut out = Vec::new();
+    while let Some(window) = pending.pop() {
+        let count = count_results(window).await?;
         if count == 0 {
             continue;
         }
 
-        if count > SEARCH_LIMIT {
-            if start == end {
-                eprintln!("Too many results from={} to={} count={}", start, end, count);
-                out.push((start, end));
-                continue;
-            }
-
-            let mid = midpoint_date(start, end);
-            if let Some(next_day) = mid.succ_opt() {
-                pending.push((next_day, end));
-            }
-            pending.push((start, mid));
+        if count > SEARCH_LIMIT
+            && let Some((first, second)) = window.split()
+        {
+            pending.push(second);
+            pending.push(first);
             continue;
         }
 
-        out.push((start, end));
+        out.push((window, count));
     }
 
     Ok(out)
 }
 
-async fn graphql_with_retry<T>(
-    client: &octocrab::Octocrab,
-    payload: &serde_json::Value,
-    context: &'static str,
-) -> anyhow::Result<T>
-where
-    T: serde::de::DeserializeOwned,
-{
-    for attempt in 1..=SEARCH_RETRIES {
-        match client.graphql::<T>(payload).await {
-            Ok(resp) => return Ok(resp),
-            Err(err) => {
-                if attempt == SEARCH_RETRIES {
-                    return Err(anyhow::Error::new(err)).context(context);
-                }
-                let backoff = 200u64.saturating_mul(1 << (attempt - 1));
-                eprintln!(
-                    "search query failed; retrying attempt={} error={}",
-                    attempt, err
-                );
-                tokio::time::sleep(Duration::from_millis(backoff)).await;
-            }
-        }
-    }
-
-    unreachable!("SEARCH_RETRIES must be >= 1");
+/// Warning for a search window whose results exceed what the search API returns.
+fn truncated_search_warning(query: &str, count: i32) -> Option<String> {
+    (count > SEARCH_LIMIT).then(|| {
+        format!(
+            "Warning: `{query}` has {count} results within one second, but the search API \
+             returns at most {SEARCH_LIMIT}; {} of them are missing from the report",
+            count - SEARCH_LIMIT
+        )
+    })
 }
 
-async fn fetch_search_count(client: &octocrab::Octocrab, query: &str) -> anyhow::Result<i32> {
+async fn fetch_search_count(client: &crate::github::Client, query: &str) -> anyhow::Result<i32> {
     let payload = serde_json::json!({
         "query": SEARCH_COUNT_QUERY,
         "variables": { "query": query },
     });
 
-    let resp = graphql_with_retry::<GraphqlResponse<serde_json::Value>>(
-        client,
-        &payload,
-        "GraphQL search query failed",
-    )
-    .await?;
+    let resp = client
+        .graphql(&payload)
+        .await
+        .context("GraphQL search query failed")?;
 
     let data = graphql_data(resp)?;
-    let search = data.get("search").expect("search response missing search");
-    let issue_count = search
-        .get("issueCount")
-        .and_then(|count| count.as_i64())
-        .context("search response missing issueCount")?;
+    let issue_count = query_value!(data.search.issueCount -> i64)
+        .required(&NodeContext::response("search count"), "search.issueCount")?;
     Ok(issue_count as i32)
 }
 
 async fn fetch_search_nodes(
-    client: &octocrab::Octocrab,
+    client: &crate::github::Client,
     query: &str,
+    since: chrono::DateTime<chrono::Utc>,
 ) -> anyhow::Result<Vec<serde_json::Value>> {
     let mut after: Option<String> = None;
     let mut out = Vec::new();
@@ -202,35 +317,24 @@ async fn fetch_search_nodes(
     for _ in 0..MAX_PAGES {
         let payload = serde_json::json!({
             "query": SEARCH_QUERY,
-            "variables": { "query": query, "after": after.clone() },
+            "variables": { "query": query, "after": after.clone(), "since": since },
         });
 
-        let resp = graphql_with_retry::<GraphqlResponse<serde_json::Value>>(
-            client,
-            &payload,
-            "GraphQL search query failed",
-        )
-        .await?;
+        let resp = client
+            .graphql(&payload)
+            .await
+            .context("GraphQL search query failed")?;
 
         let data = graphql_data(resp)?;
-        let search = data
-            .get("search")
-            .context("search response missing search")?;
-        if let Some(nodes) = search.get("nodes").and_then(|nodes| nodes.as_array()) {
+        let context = NodeContext::response("search");
+        let search = query_value!(data.search).required(&context, "search")?;
+        if let Some(nodes) = query_value!(search.nodes -> array) {
             out.extend(nodes.iter().filter(|node| !node.is_null()).cloned());
         }
 
-        let page_info = search
-            .get("pageInfo")
-            .context("search response missing pageInfo")?;
-        let has_next_page = page_info
-            .get("hasNextPage")
-            .and_then(|value| value.as_bool())
-            .context("search response missing pageInfo.hasNextPage")?;
-        let end_cursor = page_info
-            .get("endCursor")
-            .and_then(|value| value.as_str())
-            .map(|value| value.to_string());
+        let has_next_page = query_value!(search.pageInfo.hasNextPage -> bool)
+            .required(&context, "search.pageInfo.hasNextPage")?;
+        let end_cursor = query_value!(search.pageInfo.endCursor -> str).map(str::to_string);
 
         if !has_next_page {
             break;
@@ -244,22 +348,90 @@ async fn fetch_search_nodes(
     Ok(out)
 }
 
-fn search_query(query_base: &str, from: chrono::NaiveDate, to: chrono::NaiveDate) -> String {
-    format!(
-        "{query_base} involves:@me closed:{}..{}",
-        from.format("%Y-%m-%d"),
-        to.format("%Y-%m-%d")
-    )
+/// Splits `range` into chunks that fit the one-year span allowed by `contributionsCollection`.
+pub(super) fn contribution_ranges(range: DateRange) -> Vec<DateRange> {
+    let mut out = Vec::new();
+    let mut current = range.from;
+    while current <= range.to {
+        let chunk_end = (current + chrono::Duration::days(364)).min(range.to);
+        out.push(DateRange {
+            from: current,
+            to: chunk_end,
+            ..range
+        });
+        let Some(next) = chunk_end.succ_opt() else {
+            break;
+        };
+        current = next;
+    }
+    out
+}
+
+/// Pages backwards through a search node's timeline until every event since `since` is present.
+///
+/// The search query only returns the last few timeline events of each node, so older
+/// close/merge events on busy issues would otherwise be lost.
+async fn complete_timeline(
+    client: &crate::github::Client,
+    node: &mut serde_json::Value,
+    since: chrono::DateTime<chrono::Utc>,
+) -> anyhow::Result<()> {
+    let Some(id) = query_value!(node.id -> str).map(str::to_string) else {
+        return Ok(());
+    };
+    let mut before = timeline_previous_cursor(&node["timelineItems"]);
+
+    for _ in 0..MAX_PAGES {
+        let Some(cursor) = before else {
+            break;
+        };
+        let payload = serde_json::json!({
+            "query": TIMELINE_ITEMS_QUERY,
+            "variables": { "id": id, "since": since, "before": cursor },
+        });
+
+        let resp = client
+            .graphql(&payload)
+            .await
+            .context("GraphQL timeline query failed")?;
+
+        let data = graphql_data(resp)?;
+        let timeline = query_value!(data.node.timelineItems)
+            .required(&NodeContext::response("timeline"), "node.timelineItems")?;
+        before = timeline_previous_cursor(timeline);
+        prepend_timeline_nodes(node, timeline);
+    }
+
+    Ok(())
 }
 
-fn midpoint_date(from: chrono::NaiveDate, to: chrono::NaiveDate) -> chrono::NaiveDate {
-    let days = (to - from).num_days();
-    from + chrono::Duration::days(days / 2)
+fn timeline_previous_cursor(timeline: &serde_json::Value) -> Option<String> {
+    if query_value!(timeline.pageInfo.hasPreviousPage -> bool) != Some(true) {
+        return None;
+    }
+    query_value!(timeline.pageInfo.startCursor -> str).map(str::to_string)
+}
+
+fn prepend_timeline_nodes(node: &mut serde_json::Value, page: &serde_json::Value) {
+    let older = query_value!(page.nodes -> array)
+        .cloned()
+        .unwrap_or_default();
+    if let Some(nodes) = node["timelineItems"]["nodes"].as_array_mut() {
+        nodes.splice(0..0, older);
+    }
+}
+
+fn search_query(query_base: &str, field: SearchField, window: SearchWindow) -> String {
+    format!(
+        "{query_base} {}:{}..{}",
+        field.qualifier(),
+        window.start.format(SEARCH_DATETIME_FORMAT),
+        window.end.format(SEARCH_DATETIME_FORMAT)
+    )
 }
 
-pub(super) fn issue_since(from: chrono::NaiveDate) -> String {
-    let start = from.and_hms_opt(0, 0, 0).expect("invalid start of day");
-    chrono::Utc.from_utc_datetime(&start).to_rfc3339()
+pub(super) fn issue_since(range: DateRange) -> String {
+    range.start().to_rfc3339()
 }
 
 pub(super) fn graphql_data<T>(resp: GraphqlResponse<T>) -> anyhow::Result<T> {
@@ -274,35 +446,26 @@ pub(super) fn graphql_data<T>(resp: GraphqlResponse<T>) -> anyhow::Result<T> {
     resp.data.context("GraphQL response missing data")
 }
 
-pub(super) fn in_range(
-    dt: chrono::DateTime<chrono::Utc>,
-    from: chrono::NaiveDate,
-    to: chrono::NaiveDate,
-) -> bool {
-    let date = dt.date_naive();
-    if date < from {
+pub(super) fn in_range(dt: chrono::DateTime<chrono::Utc>, range: DateRange) -> bool {
+    let date = range.zone.date_of(dt);
+    if date < range.from {
         return false;
     }
-    if date > to {
+    if date > range.to {
         return false;
     }
     true
 }
 
-pub(super) fn parse_datetime(value: &str) -> anyhow::Result<chrono::DateTime<chrono::Utc>> {
-    let dt = chrono::DateTime::parse_from_rfc3339(value).context("invalid datetime")?;
-    Ok(dt.with_timezone(&chrono::Utc))
-}
-
 pub(super) async fn fetch_paginated_json<F, S>(
-    client: &octocrab::Octocrab,
+    client: &crate::github::Client,
     query: QueryKind,
     map: F,
     should_stop: S,
 ) -> anyhow::Result<Vec<EventItem>>
 where
-    F: Fn(&serde_json::Value) -> anyhow::Result<Option<EventItem>> + Copy,
-    S: Fn(&serde_json::Value) -> anyhow::Result<bool> + Copy,
+    F: Fn(&serde_json::Value) -> Result<Option<EventItem>, ShapeError> + Copy,
+    S: Fn(&serde_json::Value) -> Result<bool, ShapeError> + Copy,
 {
     let mut after: Option<String> = None;
     let mut out = Vec::new();
@@ -310,37 +473,35 @@ where
     for _ in 0..MAX_PAGES {
         let payload = serde_json::json!({
             "query": query.as_str(),
-            "variables": query.variables(after.clone()),
+            "variables": query.variables(client.login(), after.clone()),
         });
 
         let resp = client
-            .graphql::<GraphqlResponse<serde_json::Value>>(&payload)
+            .graphql(&payload)
             .await
             .context("GraphQL query failed")?;
 
         let data = graphql_data(resp)?;
-        let connection = data
-            .get("viewer")
-            .and_then(|viewer| viewer.get(query.connection_field()))
-            .context("GraphQL response missing connection")?;
-        let page_info = connection
-            .get("pageInfo")
-            .context("GraphQL response missing pageInfo")?;
-        let has_next_page = page_info
-            .get("hasNextPage")
-            .and_then(|value| value.as_bool())
-            .context("GraphQL response missing pageInfo.hasNextPage")?;
-        let end_cursor = page_info
-            .get("endCursor")
-            .and_then(|value| value.as_str())
-            .map(|value| value.to_string());
-
-        if let Some(nodes) = connection.get("nodes").and_then(|value| value.as_array()) {
-            for node in nodes.iter().filter(|node| !node.is_null()) {
-                if should_stop(node)? {
-                    return Ok(out);
+        let context = NodeContext::response(query.connection_field());
+        let connection = query_value!(data.user)
+            .and_then(|user| user.get(query.connection_field()))
+            .required(&context, "user")?;
+        let has_next_page = query_value!(connection.pageInfo.hasNextPage -> bool)
+            .required(&context, "pageInfo.hasNextPage")?;
+        let end_cursor = query_value!(connection.pageInfo.endCursor -> str).map(str::to_string);
+
+        if let Some(nodes) = query_value!(connection.nodes -> array) {
+            let mut nodes: Vec<_> = nodes.iter().filter(|node| !node.is_null()).collect();
+            if query.pages_backwards() {
+                nodes.reverse();
+            }
+            for node in nodes {
+                match skip_bad_node(should_stop(node)) {
+                    Some(true) => return Ok(out),
+                    Some(false) => {}
+                    None => continue,
                 }
-                if let Some(item) = map(node)? {
+                if let Some(item) = skip_bad_node(map(node)).flatten() {
                     out.push(item);
                 }
             }
@@ -361,6 +522,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::range::{Zone, parse_zone};
     use chrono::{NaiveDate, TimeZone, Utc};
 
     fn dt(date: &str) -> chrono::DateTime<chrono::Utc> {
@@ -375,12 +537,72 @@ mod tests {
         .unwrap()
     }
 
+    fn range(from: &str, to: &str) -> DateRange {
+        DateRange {
+            from: NaiveDate::parse_from_str(from, "%Y-%m-%d").unwrap(),
+            to: NaiveDate::parse_from_str(to, "%Y-%m-%d").unwrap(),
+            zone: Zone::UTC,
+        }
+    }
+
+    #[test]
+    fn split_windows_by_count_reports_seconds_over_the_limit() {
+        let busy = Utc.with_ymd_and_hms(2025, 1, 1, 9, 30, 15).unwrap();
+        let windows = futures::executor::block_on(split_windows_by_count(
+            range("2025-01-01", "2025-01-01"),
+            |window| async move {
+                let busy_window = window.start <= busy && busy <= window.end;
+                Ok(if busy_window { 1500 } else { 10 })
+            },
+        ))
+        .unwrap();
+
+        let over_limit: Vec<_> = windows
+            .iter()
+            .filter(|(_, count)| *count > SEARCH_LIMIT)
+            .collect();
+        assert_eq!(
+            over_limit,
+            [&(
+                SearchWindow {
+                    start: busy,
+                    end: busy
+                },
+                1500
+            )]
+        );
+        let covered: i64 = windows
+            .iter()
+            .map(|(window, _)| (window.end - window.start).num_seconds() + 1)
+            .sum();
+        assert_eq!(covered, 24 * 60 * 60);
+
+        let warning = truncated_search_warning("is:pr closed:x", 1500).unwrap();
+        assert!(warning.contains("500 of them are missing"), "{warning}");
+        assert_eq!(
+            truncated_search_warning("is:pr closed:x", SEARCH_LIMIT),
+            None
+        );
+    }
+
+    #[test]
+    fn contribution_ranges_split_by_year() {
+        let chunks = contribution_ranges(range("2024-01-01", "2025-06-30"));
+        assert_eq!(
+            chunks,
+            [
+                range("2024-01-01", "2024-12-30"),
+                range("2024-12-31", "2025-06-30")
+            ]
+        );
+        assert!(contribution_ranges(range("2025-02-01", "2025-01-01")).is_empty());
+    }
+
     #[test]
     fn in_range_accepts_inside_bounds() {
         assert!(in_range(
             dt("2025-01-02"),
-            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
-            NaiveDate::from_ymd_opt(2025, 1, 3).unwrap(),
+            range("2025-01-01", "2025-01-03")
         ));
     }
 
@@ -388,16 +610,43 @@ mod tests {
     fn in_range_rejects_outside_bounds() {
         assert!(!in_range(
             dt("2024-12-31"),
-            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
-            NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+            range("2025-01-01", "2025-01-31")
         ));
         assert!(!in_range(
             dt("2025-02-01"),
-            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
-            NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+            range("2025-01-01", "2025-01-31")
         ));
     }
 
+    #[test]
+    fn in_range_uses_zone_calendar_day() {
+        let tokyo = DateRange {
+            zone: parse_zone("Asia/Tokyo").unwrap(),
+            ..range("2025-01-02", "2025-01-02")
+        };
+        let morning = Utc.with_ymd_and_hms(2025, 1, 1, 23, 30, 0).unwrap();
+
+        assert!(in_range(morning, tokyo));
+        assert!(!in_range(morning, range("2025-01-02", "2025-01-02")));
+    }
+
+    #[test]
+    fn search_query_uses_zone_boundaries() {
+        let tokyo = DateRange {
+            zone: parse_zone("Asia/Tokyo").unwrap(),
+            ..range("2025-01-01", "2025-01-31")
+        };
+
+        assert_eq!(
+            search_query(
+                "is:pr involves:me",
+                SearchField::Closed,
+                SearchWindow::from_range(tokyo)
+            ),
+            "is:pr involves:me closed:2024-12-31T15:00:00+00:00..2025-01-31T14:59:59+00:00"
+        );
+    }
+
     #[test]
     fn event_items_from_search_node_does_not_set_body_for_closed_event() {
         let node = serde_json::json!({
@@ -414,15 +663,119 @@ mod tests {
             }
         });
 
-        let items = event_items_from_search_node(
-            &node,
-            "me",
-            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
-            NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
-        );
+        let items =
+            event_items_from_search_node(&node, "me", range("2025-01-01", "2025-01-31")).unwrap();
 
         assert_eq!(items.len(), 1);
         assert_eq!(items[0].kind, EventKind::PullRequestClosed);
         assert_eq!(items[0].body, None);
     }
+
+    #[test]
+    fn event_items_from_search_node_maps_reopened_events() {
+        let node = serde_json::json!({
+            "__typename": "Issue",
+            "url": "https://example.test/issues/1",
+            "title": "Issue A",
+            "repository": { "nameWithOwner": "o/r" },
+            "timelineItems": {
+                "nodes": [
+                    {
+                        "__typename": "ClosedEvent",
+                        "actor": { "login": "me" },
+                        "createdAt": "2025-01-10T00:00:00Z"
+                    },
+                    {
+                        "__typename": "ReopenedEvent",
+                        "actor": { "login": "me" },
+                        "createdAt": "2025-01-11T00:00:00Z"
+                    }
+                ]
+            }
+        });
+
+        let items =
+            event_items_from_search_node(&node, "me", range("2025-01-01", "2025-01-31")).unwrap();
+
+        let kinds: Vec<&EventKind> = items.iter().map(|item| &item.kind).collect();
+        assert_eq!(kinds, [&EventKind::IssueClosed, &EventKind::IssueReopened]);
+        assert_eq!(items[1].url, "https://example.test/issues/1");
+    }
+
+    #[test]
+    fn search_query_supports_updated_field() {
+        assert_eq!(
+            search_query(
+                "involves:me",
+                SearchField::Updated,
+                SearchWindow::from_range(range("2025-01-01", "2025-01-01"))
+            ),
+            "involves:me updated:2025-01-01T00:00:00+00:00..2025-01-01T23:59:59+00:00"
+        );
+    }
+
+    #[test]
+    fn prepend_timeline_nodes_keeps_chronological_order() {
+        let mut node = serde_json::json!({
+            "timelineItems": {
+                "pageInfo": { "hasPreviousPage": true, "startCursor": "c2" },
+                "nodes": [{ "createdAt": "2025-01-20T00:00:00Z" }]
+            }
+        });
+        let page = serde_json::json!({
+            "pageInfo": { "hasPreviousPage": false, "startCursor": "c1" },
+            "nodes": [
+                { "createdAt": "2025-01-05T00:00:00Z" },
+                { "createdAt": "2025-01-10T00:00:00Z" }
+            ]
+        });
+
+        assert_eq!(
+            timeline_previous_cursor(&node["timelineItems"]).as_deref(),
+            Some("c2")
+        );
+        prepend_timeline_nodes(&mut node, &page);
+
+        let dates: Vec<&str> = node["timelineItems"]["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|node| node["createdAt"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            dates,
+            [
+                "2025-01-05T00:00:00Z",
+                "2025-01-10T00:00:00Z",
+                "2025-01-20T00:00:00Z"
+            ]
+        );
+        assert_eq!(timeline_previous_cursor(&page), None);
+    }
+
+    #[test]
+    fn search_window_splits_within_a_day() {
+        let day = SearchWindow::from_range(range("2025-01-01", "2025-01-01"));
+        let (morning, evening) = day.split().unwrap();
+
+        assert_eq!(morning.start.to_rfc3339(), "2025-01-01T00:00:00+00:00");
+        assert_eq!(morning.end.to_rfc3339(), "2025-01-01T11:59:59+00:00");
+        assert_eq!(evening.start.to_rfc3339(), "2025-01-01T12:00:00+00:00");
+        assert_eq!(evening.end.to_rfc3339(), "2025-01-01T23:59:59+00:00");
+    }
+
+    #[test]
+    fn search_window_stops_splitting_at_one_second() {
+        let start = chrono::Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let (first, second) = SearchWindow {
+            start,
+            end: start + chrono::Duration::seconds(1),
+        }
+        .split()
+        .unwrap();
+
+        assert_eq!((first.start, first.end), (start, start));
+        assert_eq!(second.start, second.end);
+        assert_eq!(first.split(), None);
+    }
 }
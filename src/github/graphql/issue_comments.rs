@@ -1,29 +1,31 @@
-use super::fetch::{fetch_paginated_json, in_range, parse_datetime};
+use super::error::{NodeContext, Required};
+use super::fetch::{fetch_paginated_json, in_range};
 use super::queries::QueryKind;
 use super::types::*;
+use crate::range::DateRange;
 use valq::query_value;
 
 pub(crate) async fn query_issue_comments(
     client: &crate::github::Client,
-    from: chrono::NaiveDate,
-    to: chrono::NaiveDate,
+    range: DateRange,
 ) -> anyhow::Result<Vec<EventItem>> {
     fetch_paginated_json(
-        client.octocrab(),
+        client,
         QueryKind::IssueComments,
         move |node| {
-            let created_at = parse_datetime(
-                query_value!(node["createdAt"] -> str).expect("issue comment missing createdAt"),
-            )?;
-            if !in_range(created_at, from, to) {
+            let context = NodeContext::new("issue comments", node);
+            let created_at =
+                context.datetime(query_value!(node["createdAt"] -> str), "createdAt")?;
+            if !in_range(created_at, range) {
                 return Ok(None);
             }
-            let url = query_value!(node.url -> str).expect("issue comment missing url");
-            let body = query_value!(node.body -> str).expect("issue comment missing body");
-            let title = query_value!(node.issue.title -> str).expect("issue missing title");
-            let subject_url = query_value!(node.issue.url -> str).expect("issue missing url");
+            let url = query_value!(node.url -> str).required(&context, "url")?;
+            let body = query_value!(node.body -> str).required(&context, "body")?;
+            let title = query_value!(node.issue.title -> str).required(&context, "issue.title")?;
+            let subject_url =
+                query_value!(node.issue.url -> str).required(&context, "issue.url")?;
             let repository = query_value!(node.issue.repository["nameWithOwner"] -> str)
-                .expect("issue missing repository nameWithOwner");
+                .required(&context, "issue.repository.nameWithOwner")?;
 
             Ok(Some(EventItem {
                 kind: EventKind::IssueComment,
@@ -33,13 +35,14 @@ pub(crate) async fn query_issue_comments(
                 repository: repository.to_string(),
                 subject_title: title.to_string(),
                 subject_url: subject_url.to_string(),
+                diff_stat: None,
+                review_state: None,
             }))
         },
         move |node| {
-            let updated_at = parse_datetime(
-                query_value!(node["updatedAt"] -> str).expect("issue comment missing updatedAt"),
-            )?;
-            Ok(updated_at.date_naive() < from)
+            let updated_at = NodeContext::new("issue comments", node)
+                .datetime(query_value!(node["updatedAt"] -> str), "updatedAt")?;
+            Ok(range.zone.date_of(updated_at) < range.from)
         },
     )
     .await
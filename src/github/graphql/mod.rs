@@ -1,22 +1,34 @@
 mod closed_issues;
 mod closed_pull_requests;
+mod commits;
+mod discussion_answers;
+mod discussion_comments;
+mod error;
 mod fetch;
 mod issue_comments;
+mod opened_discussions;
 mod opened_issues;
 mod opened_pull_requests;
 mod pull_request_reviews;
 mod queries;
+mod reopened;
+mod team_members;
 mod types;
 mod viewer;
 
-pub use types::EventItem;
-#[cfg(test)]
-pub use types::EventKind;
+pub(crate) use types::GraphqlResponse;
+pub use types::{DiffStat, EventItem, EventKind, PlannedSearch, ReviewState};
 
-pub(crate) use closed_issues::query_closed_issues;
-pub(crate) use closed_pull_requests::query_closed_pull_requests;
+pub(crate) use closed_issues::{plan_closed_issues, query_closed_issues};
+pub(crate) use closed_pull_requests::{plan_closed_pull_requests, query_closed_pull_requests};
+pub(crate) use commits::query_commits;
+pub(crate) use discussion_answers::query_discussion_answers;
+pub(crate) use discussion_comments::query_discussion_comments;
 pub(crate) use issue_comments::query_issue_comments;
+pub(crate) use opened_discussions::query_opened_discussions;
 pub(crate) use opened_issues::query_opened_issues;
 pub(crate) use opened_pull_requests::query_opened_pull_requests;
 pub(crate) use pull_request_reviews::query_pull_request_review_contributions;
-pub(crate) use viewer::query_viewer_login;
+pub(crate) use reopened::{plan_reopened, query_reopened};
+pub(crate) use team_members::query_team_members;
+pub(crate) use viewer::{query_user_login, query_viewer_login};
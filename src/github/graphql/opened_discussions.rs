@@ -0,0 +1,104 @@
+use super::error::{NodeContext, Required, ShapeError};
+use super::fetch::{fetch_paginated_json, in_range};
+use super::queries::QueryKind;
+use super::types::*;
+use crate::range::DateRange;
+use valq::query_value;
+
+pub(crate) async fn query_opened_discussions(
+    client: &crate::github::Client,
+    range: DateRange,
+) -> anyhow::Result<Vec<EventItem>> {
+    fetch_paginated_json(
+        client,
+        QueryKind::OpenedDiscussions,
+        move |node| opened_discussion_event_from_node(node, range),
+        move |node| {
+            let created_at = NodeContext::new("opened discussions", node)
+                .datetime(query_value!(node["createdAt"] -> str), "createdAt")?;
+            Ok(range.zone.date_of(created_at) < range.from)
+        },
+    )
+    .await
+}
+
+fn opened_discussion_event_from_node(
+    node: &serde_json::Value,
+    range: DateRange,
+) -> Result<Option<EventItem>, ShapeError> {
+    let context = NodeContext::new("opened discussions", node);
+    let created_at = context.datetime(query_value!(node["createdAt"] -> str), "createdAt")?;
+    if !in_range(created_at, range) {
+        return Ok(None);
+    }
+    let url = query_value!(node.url -> str).required(&context, "url")?;
+    let title = query_value!(node.title -> str).required(&context, "title")?;
+    let body = query_value!(node.body -> str).map(str::to_string);
+    let repository = query_value!(node.repository["nameWithOwner"] -> str)
+        .required(&context, "repository.nameWithOwner")?;
+
+    Ok(Some(EventItem {
+        kind: EventKind::DiscussionOpened,
+        created_at,
+        url: url.to_string(),
+        body,
+        repository: repository.to_string(),
+        subject_title: title.to_string(),
+        subject_url: url.to_string(),
+        diff_stat: None,
+        review_state: None,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range() -> DateRange {
+        DateRange {
+            from: chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            to: chrono::NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+            zone: crate::range::Zone::UTC,
+        }
+    }
+
+    fn node(created_at: &str, body: Option<&str>) -> serde_json::Value {
+        serde_json::json!({
+            "createdAt": created_at,
+            "url": "https://example.test/discussions/1",
+            "title": "RFC: API v2",
+            "body": body,
+            "repository": { "nameWithOwner": "o/rfcs" }
+        })
+    }
+
+    #[test]
+    fn opened_discussion_event_is_its_own_subject() {
+        let event = opened_discussion_event_from_node(
+            &node("2025-01-10T00:00:00Z", Some("Proposal")),
+            range(),
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(event.kind, EventKind::DiscussionOpened);
+        assert_eq!(event.body.as_deref(), Some("Proposal"));
+        assert_eq!(event.subject_title, "RFC: API v2");
+        assert_eq!(event.subject_url, event.url);
+        assert_eq!(event.repository, "o/rfcs");
+    }
+
+    #[test]
+    fn opened_discussion_event_allows_a_missing_body_and_skips_other_periods() {
+        let event = opened_discussion_event_from_node(&node("2025-01-10T00:00:00Z", None), range())
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.body, None);
+
+        assert!(
+            opened_discussion_event_from_node(&node("2025-02-01T00:00:00Z", None), range())
+                .unwrap()
+                .is_none()
+        );
+    }
+}
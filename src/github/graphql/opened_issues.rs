@@ -1,24 +1,24 @@
-use super::fetch::{fetch_paginated_json, in_range, issue_since, parse_datetime};
+use super::error::{NodeContext, Required, ShapeError};
+use super::fetch::{fetch_paginated_json, in_range, issue_since};
 use super::queries::QueryKind;
 use super::types::*;
+use crate::range::DateRange;
 use valq::query_value;
 
 pub(crate) async fn query_opened_issues(
     client: &crate::github::Client,
-    from: chrono::NaiveDate,
-    to: chrono::NaiveDate,
+    range: DateRange,
 ) -> anyhow::Result<Vec<EventItem>> {
     fetch_paginated_json(
-        client.octocrab(),
+        client,
         QueryKind::OpenedIssues {
-            since: issue_since(from),
+            since: issue_since(range),
         },
-        move |node| opened_issue_event_from_node(node, from, to),
+        move |node| opened_issue_event_from_node(node, range),
         move |node| {
-            let created_at = parse_datetime(
-                query_value!(node["createdAt"] -> str).expect("issue missing createdAt"),
-            )?;
-            Ok(created_at.date_naive() < from)
+            let created_at = NodeContext::new("opened issues", node)
+                .datetime(query_value!(node["createdAt"] -> str), "createdAt")?;
+            Ok(range.zone.date_of(created_at) < range.from)
         },
     )
     .await
@@ -26,19 +26,18 @@ pub(crate) async fn query_opened_issues(
 
 fn opened_issue_event_from_node(
     node: &serde_json::Value,
-    from: chrono::NaiveDate,
-    to: chrono::NaiveDate,
-) -> anyhow::Result<Option<EventItem>> {
-    let created_at =
-        parse_datetime(query_value!(node["createdAt"] -> str).expect("issue missing createdAt"))?;
-    if !in_range(created_at, from, to) {
+    range: DateRange,
+) -> Result<Option<EventItem>, ShapeError> {
+    let context = NodeContext::new("opened issues", node);
+    let created_at = context.datetime(query_value!(node["createdAt"] -> str), "createdAt")?;
+    if !in_range(created_at, range) {
         return Ok(None);
     }
-    let url = query_value!(node.url -> str).expect("issue missing url");
-    let title = query_value!(node.title -> str).expect("issue missing title");
+    let url = query_value!(node.url -> str).required(&context, "url")?;
+    let title = query_value!(node.title -> str).required(&context, "title")?;
     let body = query_value!(node.body -> str).map(str::to_string);
     let repository = query_value!(node.repository["nameWithOwner"] -> str)
-        .expect("issue missing repository nameWithOwner");
+        .required(&context, "repository.nameWithOwner")?;
 
     Ok(Some(EventItem {
         kind: EventKind::IssueOpened,
@@ -48,6 +47,8 @@ fn opened_issue_event_from_node(
         repository: repository.to_string(),
         subject_title: title.to_string(),
         subject_url: url.to_string(),
+        diff_stat: None,
+        review_state: None,
     }))
 }
 
@@ -67,8 +68,11 @@ mod tests {
 
         let event = opened_issue_event_from_node(
             &node,
-            chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
-            chrono::NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+            DateRange {
+                from: chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+                to: chrono::NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+                zone: crate::range::Zone::UTC,
+            },
         )
         .unwrap()
         .unwrap();
@@ -76,4 +80,31 @@ mod tests {
         assert_eq!(event.kind, EventKind::IssueOpened);
         assert_eq!(event.body.as_deref(), Some("first line\nsecond line"));
     }
+
+    #[test]
+    fn opened_issue_event_reports_missing_fields() {
+        // Issues in deleted repositories come back with a null repository.
+        let node = serde_json::json!({
+            "createdAt": "2025-01-10T00:00:00Z",
+            "url": "https://example.test/issues/1",
+            "title": "Issue A",
+            "repository": null
+        });
+
+        let error = opened_issue_event_from_node(
+            &node,
+            DateRange {
+                from: chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+                to: chrono::NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+                zone: crate::range::Zone::UTC,
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(error.field, "repository.nameWithOwner");
+        assert_eq!(
+            error.node_url.as_deref(),
+            Some("https://example.test/issues/1")
+        );
+    }
 }
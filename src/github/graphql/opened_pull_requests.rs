@@ -1,22 +1,22 @@
-use super::fetch::{fetch_paginated_json, in_range, parse_datetime};
+use super::error::{NodeContext, Required, ShapeError};
+use super::fetch::{fetch_paginated_json, in_range};
 use super::queries::QueryKind;
 use super::types::*;
+use crate::range::DateRange;
 use valq::query_value;
 
 pub(crate) async fn query_opened_pull_requests(
     client: &crate::github::Client,
-    from: chrono::NaiveDate,
-    to: chrono::NaiveDate,
+    range: DateRange,
 ) -> anyhow::Result<Vec<EventItem>> {
     fetch_paginated_json(
-        client.octocrab(),
+        client,
         QueryKind::OpenedPullRequests,
-        move |node| opened_pull_request_event_from_node(node, from, to),
+        move |node| opened_pull_request_event_from_node(node, range),
         move |node| {
-            let created_at = parse_datetime(
-                query_value!(node["createdAt"] -> str).expect("pull request missing createdAt"),
-            )?;
-            Ok(created_at.date_naive() < from)
+            let created_at = NodeContext::new("opened pull requests", node)
+                .datetime(query_value!(node["createdAt"] -> str), "createdAt")?;
+            Ok(range.zone.date_of(created_at) < range.from)
         },
     )
     .await
@@ -24,20 +24,18 @@ pub(crate) async fn query_opened_pull_requests(
 
 fn opened_pull_request_event_from_node(
     node: &serde_json::Value,
-    from: chrono::NaiveDate,
-    to: chrono::NaiveDate,
-) -> anyhow::Result<Option<EventItem>> {
-    let created_at = parse_datetime(
-        query_value!(node["createdAt"] -> str).expect("pull request missing createdAt"),
-    )?;
-    if !in_range(created_at, from, to) {
+    range: DateRange,
+) -> Result<Option<EventItem>, ShapeError> {
+    let context = NodeContext::new("opened pull requests", node);
+    let created_at = context.datetime(query_value!(node["createdAt"] -> str), "createdAt")?;
+    if !in_range(created_at, range) {
         return Ok(None);
     }
-    let url = query_value!(node.url -> str).expect("pull request missing url");
-    let title = query_value!(node.title -> str).expect("pull request missing title");
+    let url = query_value!(node.url -> str).required(&context, "url")?;
+    let title = query_value!(node.title -> str).required(&context, "title")?;
     let body = query_value!(node.body -> str).map(str::to_string);
     let repository = query_value!(node.repository["nameWithOwner"] -> str)
-        .expect("pull request missing repository nameWithOwner");
+        .required(&context, "repository.nameWithOwner")?;
 
     Ok(Some(EventItem {
         kind: EventKind::PullRequestOpened,
@@ -47,6 +45,8 @@ fn opened_pull_request_event_from_node(
         repository: repository.to_string(),
         subject_title: title.to_string(),
         subject_url: url.to_string(),
+        diff_stat: None,
+        review_state: None,
     }))
 }
 
@@ -66,8 +66,11 @@ mod tests {
 
         let event = opened_pull_request_event_from_node(
             &node,
-            chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
-            chrono::NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+            DateRange {
+                from: chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+                to: chrono::NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+                zone: crate::range::Zone::UTC,
+            },
         )
         .unwrap()
         .unwrap();
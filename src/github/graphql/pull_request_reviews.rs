@@ -1,31 +1,18 @@
-use super::fetch::{MAX_PAGES, graphql_data, in_range, parse_datetime};
-use super::queries::REVIEW_CONTRIBUTIONS_QUERY;
+use super::error::{NodeContext, Required, ShapeError, skip_bad_node};
+use super::fetch::{MAX_PAGES, contribution_ranges, graphql_data, in_range};
+use super::queries::{REVIEW_COMMENTS_QUERY, REVIEW_CONTRIBUTIONS_QUERY};
 use super::types::*;
+use crate::range::DateRange;
 use anyhow::Context;
-use chrono::TimeZone;
 use valq::query_value;
 
 pub(crate) async fn query_pull_request_review_contributions(
     client: &crate::github::Client,
-    from: chrono::NaiveDate,
-    to: chrono::NaiveDate,
+    range: DateRange,
 ) -> anyhow::Result<Vec<EventItem>> {
-    if from > to {
-        return Ok(Vec::new());
-    }
-
     let mut out = Vec::new();
-    let mut current = from;
-    while current <= to {
-        let candidate = current + chrono::Duration::days(364);
-        let chunk_end = if candidate > to { to } else { candidate };
-        out.extend(
-            query_pull_request_review_contributions_range(client, current, chunk_end).await?,
-        );
-        let Some(next) = chunk_end.succ_opt() else {
-            break;
-        };
-        current = next;
+    for chunk in contribution_ranges(range) {
+        out.extend(query_pull_request_review_contributions_range(client, chunk).await?);
     }
 
     Ok(out)
@@ -33,107 +20,70 @@ pub(crate) async fn query_pull_request_review_contributions(
 
 async fn query_pull_request_review_contributions_range(
     client: &crate::github::Client,
-    from: chrono::NaiveDate,
-    to: chrono::NaiveDate,
+    range: DateRange,
 ) -> anyhow::Result<Vec<EventItem>> {
-    let from_dt = from.and_hms_opt(0, 0, 0).context("invalid start of day")?;
-    let to_dt = to.and_hms_opt(23, 59, 59).context("invalid end of day")?;
-    let from_dt = chrono::Utc.from_utc_datetime(&from_dt);
-    let to_dt = chrono::Utc.from_utc_datetime(&to_dt);
+    let from_dt = range.start();
+    let to_dt = range.end();
     let mut after: Option<String> = None;
     let mut out = Vec::new();
 
     for _ in 0..MAX_PAGES {
         let payload = serde_json::json!({
             "query": REVIEW_CONTRIBUTIONS_QUERY,
-            "variables": { "from": from_dt, "to": to_dt, "after": after.clone() },
+            "variables": {
+                "login": client.login(),
+                "from": from_dt,
+                "to": to_dt,
+                "after": after.clone(),
+            },
         });
 
         let resp = client
-            .octocrab()
-            .graphql::<GraphqlResponse<serde_json::Value>>(&payload)
+            .graphql(&payload)
             .await
             .context("GraphQL review contributions query failed")?;
 
         let data = graphql_data(resp)?;
-        let connection = data
-            .get("viewer")
-            .and_then(|viewer| viewer.get("contributionsCollection"))
-            .and_then(|collection| collection.get("pullRequestReviewContributions"))
-            .expect("review contributions response missing connection");
+        let context = NodeContext::response("review contributions");
+        let connection = query_value!(
+            data.user
+                .contributionsCollection
+                .pullRequestReviewContributions
+        )
+        .required(
+            &context,
+            "user.contributionsCollection.pullRequestReviewContributions",
+        )?;
 
-        if let Some(nodes) = connection.get("nodes").and_then(|nodes| nodes.as_array()) {
+        if let Some(nodes) = query_value!(connection.nodes -> array) {
             for node in nodes.iter().filter(|node| !node.is_null()) {
-                let occurred_at = parse_datetime(
-                    query_value!(node["occurredAt"] -> str)
-                        .expect("review contribution missing occurredAt"),
-                )?;
-
-                let Some(review) = query_value!(node.pullRequestReview) else {
+                let Some(review) =
+                    skip_bad_node(review_contribution_from_node(node, range)).flatten()
+                else {
                     continue;
                 };
+                out.extend(review.event);
+                out.extend(review_comment_events(
+                    review.comments,
+                    &review.subject,
+                    range,
+                ));
 
-                let review_url = query_value!(review.url -> str).expect("review missing url");
-                let review_body = query_value!(review.body -> str).map(str::to_string);
-                let pull_request =
-                    query_value!(review.pullRequest).expect("review missing pullRequest");
-                let subject_title =
-                    query_value!(pull_request.title -> str).expect("pull request missing title");
-                let subject_url =
-                    query_value!(pull_request.url -> str).expect("pull request missing url");
-                let repository = query_value!(pull_request.repository["nameWithOwner"] -> str)
-                    .expect("pull request missing repository nameWithOwner");
-
-                if in_range(occurred_at, from, to) {
-                    out.push(EventItem {
-                        kind: EventKind::PullRequestReview,
-                        created_at: occurred_at,
-                        url: review_url.to_string(),
-                        body: review_body.clone(),
-                        repository: repository.to_string(),
-                        subject_title: subject_title.to_string(),
-                        subject_url: subject_url.to_string(),
-                    });
-                }
-
-                if let Some(comments) = query_value!(review.comments.nodes -> array) {
-                    for comment in comments.iter() {
-                        let created_at = parse_datetime(
-                            query_value!(comment["createdAt"] -> str)
-                                .expect("review comment missing createdAt"),
-                        )?;
-                        if !in_range(created_at, from, to) {
-                            continue;
-                        }
-                        let comment_url =
-                            query_value!(comment.url -> str).expect("review comment missing url");
-                        let comment_body =
-                            query_value!(comment.body -> str).expect("review comment missing body");
-                        out.push(EventItem {
-                            kind: EventKind::PullRequestReviewComment,
-                            created_at,
-                            url: comment_url.to_string(),
-                            body: Some(comment_body.to_string()),
-                            repository: repository.to_string(),
-                            subject_title: subject_title.to_string(),
-                            subject_url: subject_url.to_string(),
-                        });
-                    }
+                let mut comments_after = next_page_cursor(review.comments);
+                for _ in 0..MAX_PAGES {
+                    let Some(cursor) = comments_after else {
+                        break;
+                    };
+                    let comments = query_review_comments_page(client, review.id, &cursor).await?;
+                    out.extend(review_comment_events(&comments, &review.subject, range));
+                    comments_after = next_page_cursor(&comments);
                 }
             }
         }
 
-        let page_info = connection
-            .get("pageInfo")
-            .expect("review contributions response missing pageInfo");
-        let has_next_page = page_info
-            .get("hasNextPage")
-            .and_then(|value| value.as_bool())
-            .expect("review contributions response missing pageInfo.hasNextPage");
-        let end_cursor = page_info
-            .get("endCursor")
-            .and_then(|value| value.as_str())
-            .map(|value| value.to_string());
+        let has_next_page = query_value!(connection.pageInfo.hasNextPage -> bool)
+            .required(&context, "pageInfo.hasNextPage")?;
+        let end_cursor = query_value!(connection.pageInfo.endCursor -> str).map(str::to_string);
 
         if !has_next_page {
             break;
@@ -146,3 +96,175 @@ async fn query_pull_request_review_contributions_range(
 
     Ok(out)
 }
+
+/// Pull request a review and its comments belong to.
+struct ReviewSubject<'a> {
+    repository: &'a str,
+    subject_title: &'a str,
+    subject_url: &'a str,
+}
+
+/// A review contribution; `event` is `None` when the review falls outside the range
+/// but its comments may not.
+struct ReviewContribution<'a> {
+    id: &'a str,
+    event: Option<EventItem>,
+    subject: ReviewSubject<'a>,
+    comments: &'a serde_json::Value,
+}
+
+fn review_contribution_from_node(
+    node: &serde_json::Value,
+    range: DateRange,
+) -> Result<Option<ReviewContribution<'_>>, ShapeError> {
+    let Some(review) = query_value!(node.pullRequestReview) else {
+        return Ok(None);
+    };
+    let context = NodeContext::new("review contributions", review);
+    let occurred_at = context.datetime(query_value!(node["occurredAt"] -> str), "occurredAt")?;
+
+    let id = query_value!(review.id -> str).required(&context, "pullRequestReview.id")?;
+    let review_url = query_value!(review.url -> str).required(&context, "pullRequestReview.url")?;
+    let review_body = query_value!(review.body -> str).map(str::to_string);
+    let review_state = query_value!(review.state -> str).and_then(ReviewState::from_graphql);
+    let subject = ReviewSubject {
+        subject_title: query_value!(review.pullRequest.title -> str)
+            .required(&context, "pullRequestReview.pullRequest.title")?,
+        subject_url: query_value!(review.pullRequest.url -> str)
+            .required(&context, "pullRequestReview.pullRequest.url")?,
+        repository: query_value!(review.pullRequest.repository["nameWithOwner"] -> str).required(
+            &context,
+            "pullRequestReview.pullRequest.repository.nameWithOwner",
+        )?,
+    };
+    let comments =
+        query_value!(review.comments).required(&context, "pullRequestReview.comments")?;
+
+    let event = in_range(occurred_at, range).then(|| EventItem {
+        kind: EventKind::PullRequestReview,
+        created_at: occurred_at,
+        url: review_url.to_string(),
+        body: review_body,
+        repository: subject.repository.to_string(),
+        subject_title: subject.subject_title.to_string(),
+        subject_url: subject.subject_url.to_string(),
+        diff_stat: None,
+        review_state,
+    });
+
+    Ok(Some(ReviewContribution {
+        id,
+        event,
+        subject,
+        comments,
+    }))
+}
+
+fn review_comment_events(
+    comments: &serde_json::Value,
+    subject: &ReviewSubject<'_>,
+    range: DateRange,
+) -> Vec<EventItem> {
+    query_value!(comments.nodes -> array)
+        .into_iter()
+        .flatten()
+        .filter(|comment| !comment.is_null())
+        .filter_map(|comment| {
+            skip_bad_node(review_comment_event(comment, subject, range)).flatten()
+        })
+        .collect()
+}
+
+fn review_comment_event(
+    comment: &serde_json::Value,
+    subject: &ReviewSubject<'_>,
+    range: DateRange,
+) -> Result<Option<EventItem>, ShapeError> {
+    let context = NodeContext::new("review comments", comment);
+    let created_at = context.datetime(query_value!(comment["createdAt"] -> str), "createdAt")?;
+    if !in_range(created_at, range) {
+        return Ok(None);
+    }
+    let comment_url = query_value!(comment.url -> str).required(&context, "url")?;
+    let comment_body = query_value!(comment.body -> str).required(&context, "body")?;
+    Ok(Some(EventItem {
+        kind: EventKind::PullRequestReviewComment,
+        created_at,
+        url: comment_url.to_string(),
+        body: Some(comment_body.to_string()),
+        repository: subject.repository.to_string(),
+        subject_title: subject.subject_title.to_string(),
+        subject_url: subject.subject_url.to_string(),
+        diff_stat: None,
+        review_state: None,
+    }))
+}
+
+fn next_page_cursor(connection: &serde_json::Value) -> Option<String> {
+    if query_value!(connection.pageInfo.hasNextPage -> bool) != Some(true) {
+        return None;
+    }
+    query_value!(connection.pageInfo.endCursor -> str).map(str::to_string)
+}
+
+async fn query_review_comments_page(
+    client: &crate::github::Client,
+    review_id: &str,
+    after: &str,
+) -> anyhow::Result<serde_json::Value> {
+    let payload = serde_json::json!({
+        "query": REVIEW_COMMENTS_QUERY,
+        "variables": { "id": review_id, "after": after },
+    });
+
+    let resp = client
+        .graphql(&payload)
+        .await
+        .context("GraphQL review comments query failed")?;
+
+    let data = graphql_data(resp)?;
+    let comments = query_value!(data.node.comments)
+        .required(&NodeContext::response("review comments"), "node.comments")?;
+    Ok(comments.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn review_comment_events_filter_by_range_and_report_next_page() {
+        let comments = serde_json::json!({
+            "pageInfo": { "hasNextPage": true, "endCursor": "c100" },
+            "nodes": [
+                {
+                    "createdAt": "2024-12-31T23:00:00Z",
+                    "url": "https://example.test/pull/1#discussion_r1",
+                    "body": "before"
+                },
+                {
+                    "createdAt": "2025-01-02T00:00:00Z",
+                    "url": "https://example.test/pull/1#discussion_r2",
+                    "body": "inside"
+                }
+            ]
+        });
+        let subject = ReviewSubject {
+            repository: "o/r",
+            subject_title: "PR A",
+            subject_url: "https://example.test/pull/1",
+        };
+        let range = DateRange {
+            from: chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            to: chrono::NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+            zone: crate::range::Zone::UTC,
+        };
+
+        let events = review_comment_events(&comments, &subject, range);
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, EventKind::PullRequestReviewComment);
+        assert_eq!(events[0].body.as_deref(), Some("inside"));
+        assert_eq!(next_page_cursor(&comments).as_deref(), Some("c100"));
+    }
+}
@@ -1,20 +1,32 @@
 use serde_json::Value;
 
+pub(crate) const COMMIT_CONTRIBUTIONS_QUERY: &str =
+    include_str!("queries/commit_contributions.graphql");
+pub(crate) const COMMIT_HISTORY_QUERY: &str = include_str!("queries/commit_history.graphql");
+pub(crate) const DISCUSSION_COMMENTS_QUERY: &str =
+    include_str!("queries/discussion_comments.graphql");
 pub(crate) const ISSUE_COMMENTS_QUERY: &str = include_str!("queries/issue_comments.graphql");
 pub(crate) const OPENED_ISSUES_SINCE_QUERY: &str =
     include_str!("queries/opened_issues_since.graphql");
+pub(crate) const OPENED_DISCUSSIONS_QUERY: &str =
+    include_str!("queries/opened_discussions.graphql");
 pub(crate) const OPENED_PULL_REQUESTS_QUERY: &str =
     include_str!("queries/opened_pull_requests.graphql");
 
+pub(crate) const REVIEW_COMMENTS_QUERY: &str = include_str!("queries/review_comments.graphql");
 pub(crate) const REVIEW_CONTRIBUTIONS_QUERY: &str =
     include_str!("queries/review_contributions.graphql");
 pub(crate) const SEARCH_QUERY: &str = include_str!("queries/search.graphql");
 pub(crate) const SEARCH_COUNT_QUERY: &str = include_str!("queries/search_count.graphql");
+pub(crate) const TEAM_MEMBERS_QUERY: &str = include_str!("queries/team_members.graphql");
+pub(crate) const TIMELINE_ITEMS_QUERY: &str = include_str!("queries/timeline_items.graphql");
 
 pub(crate) enum QueryKind {
     IssueComments,
     OpenedIssues { since: String },
     OpenedPullRequests,
+    OpenedDiscussions,
+    DiscussionComments { only_answers: bool },
 }
 
 impl QueryKind {
@@ -23,23 +35,36 @@ impl QueryKind {
             QueryKind::IssueComments => ISSUE_COMMENTS_QUERY,
             QueryKind::OpenedIssues { .. } => OPENED_ISSUES_SINCE_QUERY,
             QueryKind::OpenedPullRequests => OPENED_PULL_REQUESTS_QUERY,
+            QueryKind::OpenedDiscussions => OPENED_DISCUSSIONS_QUERY,
+            QueryKind::DiscussionComments { .. } => DISCUSSION_COMMENTS_QUERY,
         }
     }
 
-    pub(crate) fn variables(&self, after: Option<String>) -> Value {
+    pub(crate) fn variables(&self, login: &str, after: Option<String>) -> Value {
         match self {
             QueryKind::OpenedIssues { since } => {
-                serde_json::json!({ "after": after, "since": since })
+                serde_json::json!({ "login": login, "after": after, "since": since })
             }
-            _ => serde_json::json!({ "after": after }),
+            QueryKind::DiscussionComments { only_answers } => {
+                serde_json::json!({ "login": login, "before": after, "onlyAnswers": only_answers })
+            }
+            _ => serde_json::json!({ "login": login, "after": after }),
         }
     }
 
+    /// Whether pages are fetched from the end of the connection. Nodes within each page are
+    /// then still listed in the connection's order.
+    pub(crate) fn pages_backwards(&self) -> bool {
+        matches!(self, QueryKind::DiscussionComments { .. })
+    }
+
     pub(crate) fn connection_field(&self) -> &'static str {
         match self {
             QueryKind::IssueComments => "issueComments",
             QueryKind::OpenedIssues { .. } => "issues",
             QueryKind::OpenedPullRequests => "pullRequests",
+            QueryKind::OpenedDiscussions => "repositoryDiscussions",
+            QueryKind::DiscussionComments { .. } => "repositoryDiscussionComments",
         }
     }
 }
@@ -0,0 +1,72 @@
+use super::error::skip_bad_node;
+use super::fetch::{
+    SearchField, event_items_from_search_node, fetch_search_nodes_range, plan_search_range,
+};
+use super::types::{EventItem, EventKind, PlannedSearch};
+use crate::range::DateRange;
+use chrono::NaiveDate;
+
+/// Issues and pull requests reopened within the range.
+///
+/// Reopened items are often still open, so they cannot be found with the `closed:` search
+/// used for close events. Anything reopened in the range has been updated since its start,
+/// so every item updated from `range.from` until `today` is searched instead.
+pub(crate) async fn query_reopened(
+    client: &crate::github::Client,
+    range: DateRange,
+    today: NaiveDate,
+    search_filter: &str,
+) -> anyhow::Result<Vec<EventItem>> {
+    if range.from > range.to {
+        return Ok(Vec::new());
+    }
+
+    let nodes = fetch_search_nodes_range(
+        client,
+        "",
+        SearchField::Updated,
+        search_filter,
+        updated_range(range, today),
+    )
+    .await?;
+
+    Ok(nodes
+        .into_iter()
+        .flat_map(|node| {
+            skip_bad_node(event_items_from_search_node(&node, client.login(), range))
+                .unwrap_or_default()
+        })
+        .filter(|item| {
+            matches!(
+                item.kind,
+                EventKind::IssueReopened | EventKind::PullRequestReopened
+            )
+        })
+        .collect())
+}
+
+pub(crate) async fn plan_reopened(
+    client: &crate::github::Client,
+    range: DateRange,
+    today: NaiveDate,
+    search_filter: &str,
+) -> anyhow::Result<Vec<PlannedSearch>> {
+    if range.from > range.to {
+        return Ok(Vec::new());
+    }
+    plan_search_range(
+        client,
+        "",
+        SearchField::Updated,
+        search_filter,
+        updated_range(range, today),
+    )
+    .await
+}
+
+fn updated_range(range: DateRange, today: NaiveDate) -> DateRange {
+    DateRange {
+        to: range.to.max(today),
+        ..range
+    }
+}
@@ -0,0 +1,54 @@
+use super::error::{NodeContext, Required};
+use super::fetch::{MAX_PAGES, graphql_data};
+use super::queries::TEAM_MEMBERS_QUERY;
+use anyhow::Context;
+use valq::query_value;
+
+/// Lists the logins of every member of `org/slug`, including child team members.
+pub(crate) async fn query_team_members(
+    client: &crate::github::Client,
+    org: &str,
+    slug: &str,
+) -> anyhow::Result<Vec<String>> {
+    let mut after: Option<String> = None;
+    let mut out = Vec::new();
+
+    for _ in 0..MAX_PAGES {
+        let payload = serde_json::json!({
+            "query": TEAM_MEMBERS_QUERY,
+            "variables": { "org": org, "slug": slug, "after": after.clone() },
+        });
+
+        let resp = client
+            .graphql(&payload)
+            .await
+            .context("GraphQL team members query failed")?;
+
+        let data = graphql_data(resp)?;
+        let members = query_value!(data.organization.team.members)
+            .with_context(|| format!("team {org}/{slug} not found"))?;
+
+        if let Some(nodes) = query_value!(members.nodes -> array) {
+            out.extend(
+                nodes
+                    .iter()
+                    .filter_map(|node| query_value!(node.login -> str))
+                    .map(str::to_string),
+            );
+        }
+
+        let has_next_page = query_value!(members.pageInfo.hasNextPage -> bool).required(
+            &NodeContext::response("team members"),
+            "pageInfo.hasNextPage",
+        )?;
+        if !has_next_page {
+            break;
+        }
+        after = query_value!(members.pageInfo.endCursor -> str).map(str::to_string);
+        if after.is_none() {
+            break;
+        }
+    }
+
+    Ok(out)
+}
@@ -2,22 +2,72 @@
 pub enum EventKind {
     IssueOpened,
     IssueClosed,
+    IssueReopened,
     IssueComment,
     PullRequestOpened,
     PullRequestClosed,
     PullRequestMerged,
+    PullRequestReopened,
     PullRequestReview,
     PullRequestReviewComment,
+    Commit,
+    DiscussionOpened,
+    DiscussionComment,
+    DiscussionAnswerMarked,
 }
 
 impl EventKind {
+    pub const ALL: [EventKind; 14] = [
+        EventKind::IssueOpened,
+        EventKind::IssueClosed,
+        EventKind::IssueReopened,
+        EventKind::IssueComment,
+        EventKind::PullRequestOpened,
+        EventKind::PullRequestClosed,
+        EventKind::PullRequestMerged,
+        EventKind::PullRequestReopened,
+        EventKind::PullRequestReview,
+        EventKind::PullRequestReviewComment,
+        EventKind::Commit,
+        EventKind::DiscussionOpened,
+        EventKind::DiscussionComment,
+        EventKind::DiscussionAnswerMarked,
+    ];
+
     pub fn action_label(&self) -> &'static str {
         match self {
-            EventKind::IssueComment | EventKind::PullRequestReviewComment => "Comment",
+            EventKind::IssueComment
+            | EventKind::PullRequestReviewComment
+            | EventKind::DiscussionComment => "Comment",
             EventKind::PullRequestReview => "Reviewed",
-            EventKind::IssueOpened | EventKind::PullRequestOpened => "Opened",
+            EventKind::IssueOpened | EventKind::PullRequestOpened | EventKind::DiscussionOpened => {
+                "Opened"
+            }
             EventKind::IssueClosed | EventKind::PullRequestClosed => "Closed",
             EventKind::PullRequestMerged => "Merged",
+            EventKind::IssueReopened | EventKind::PullRequestReopened => "Reopened",
+            EventKind::Commit => "Committed",
+            EventKind::DiscussionAnswerMarked => "Answer marked",
+        }
+    }
+
+    /// Stable machine-readable name used by structured output formats.
+    pub fn name(&self) -> &'static str {
+        match self {
+            EventKind::IssueOpened => "issue_opened",
+            EventKind::IssueClosed => "issue_closed",
+            EventKind::IssueComment => "issue_comment",
+            EventKind::PullRequestOpened => "pull_request_opened",
+            EventKind::PullRequestClosed => "pull_request_closed",
+            EventKind::PullRequestMerged => "pull_request_merged",
+            EventKind::IssueReopened => "issue_reopened",
+            EventKind::PullRequestReopened => "pull_request_reopened",
+            EventKind::PullRequestReview => "pull_request_review",
+            EventKind::PullRequestReviewComment => "pull_request_review_comment",
+            EventKind::Commit => "commit",
+            EventKind::DiscussionOpened => "discussion_opened",
+            EventKind::DiscussionComment => "discussion_comment",
+            EventKind::DiscussionAnswerMarked => "discussion_answer_marked",
         }
     }
 }
@@ -31,15 +81,108 @@ pub struct EventItem {
     pub repository: String,
     pub subject_title: String,
     pub subject_url: String,
+    pub diff_stat: Option<DiffStat>,
+    pub review_state: Option<ReviewState>,
+}
+
+impl EventItem {
+    /// Label shown for this event; reviews are labelled by how they ended.
+    pub fn action_label(&self) -> &'static str {
+        match self.review_state {
+            Some(state) => state.action_label(),
+            None => self.kind.action_label(),
+        }
+    }
 }
 
-#[derive(Debug, serde::Deserialize)]
+/// Final state of a pull request review.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewState {
+    Approved,
+    ChangesRequested,
+    Commented,
+    Dismissed,
+}
+
+impl ReviewState {
+    pub const ALL: [ReviewState; 4] = [
+        ReviewState::Approved,
+        ReviewState::ChangesRequested,
+        ReviewState::Commented,
+        ReviewState::Dismissed,
+    ];
+
+    /// Parses a GraphQL `PullRequestReviewState`; pending reviews have no state.
+    pub fn from_graphql(value: &str) -> Option<Self> {
+        match value {
+            "APPROVED" => Some(ReviewState::Approved),
+            "CHANGES_REQUESTED" => Some(ReviewState::ChangesRequested),
+            "COMMENTED" => Some(ReviewState::Commented),
+            "DISMISSED" => Some(ReviewState::Dismissed),
+            _ => None,
+        }
+    }
+
+    pub fn action_label(&self) -> &'static str {
+        match self {
+            ReviewState::Approved => "Approved",
+            ReviewState::ChangesRequested => "Requested changes",
+            ReviewState::Commented => "Reviewed",
+            ReviewState::Dismissed => "Review dismissed",
+        }
+    }
+
+    /// Stable machine-readable name used by structured output formats.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReviewState::Approved => "approved",
+            ReviewState::ChangesRequested => "changes_requested",
+            ReviewState::Commented => "commented",
+            ReviewState::Dismissed => "dismissed",
+        }
+    }
+}
+
+/// Lines added and removed by a commit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffStat {
+    pub additions: u64,
+    pub deletions: u64,
+}
+
+/// A search that a query would run, with the number of results GitHub reports for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedSearch {
+    pub query: String,
+    pub count: u64,
+}
+
+impl PlannedSearch {
+    /// Search result pages needed to fetch every node.
+    pub fn pages(&self) -> u64 {
+        self.count.div_ceil(super::fetch::SEARCH_PAGE_SIZE).max(1)
+    }
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub(crate) struct GraphqlResponse<T> {
     pub data: Option<T>,
     pub errors: Option<Vec<GraphqlError>>,
 }
 
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub(crate) struct GraphqlError {
     pub message: String,
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+}
+
+impl<T> GraphqlResponse<T> {
+    /// Whether the query was rejected for exceeding the GraphQL rate limit.
+    pub(crate) fn is_rate_limited(&self) -> bool {
+        self.errors
+            .iter()
+            .flatten()
+            .any(|error| error.kind.as_deref() == Some("RATE_LIMITED"))
+    }
 }
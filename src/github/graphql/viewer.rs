@@ -1,17 +1,42 @@
+use super::error::{NodeContext, Required};
 use super::fetch::graphql_data;
-use super::types::GraphqlResponse;
 use anyhow::Context;
 use valq::query_value;
 
-pub(crate) async fn query_viewer_login(client: &octocrab::Octocrab) -> anyhow::Result<String> {
-    let payload = serde_json::json!({ "query": "query { viewer { login } }" });
+pub(crate) async fn query_viewer_login(client: &crate::github::Client) -> anyhow::Result<String> {
+    let payload = serde_json::json!({
+        "query": "query { viewer { login } rateLimit { cost remaining resetAt } }",
+    });
 
-    let resp: GraphqlResponse<serde_json::Value> = client
+    let resp = client
         .graphql(&payload)
         .await
         .context("GraphQL viewer query failed")?;
 
     let data = graphql_data(resp)?;
-    let login = query_value!(data.viewer.login -> str).expect("viewer response missing login");
+    let login = query_value!(data.viewer.login -> str)
+        .required(&NodeContext::response("viewer"), "viewer.login")?;
+    Ok(login.to_string())
+}
+
+/// Resolves `login` to the user's canonical login, failing if the user does not exist.
+pub(crate) async fn query_user_login(
+    client: &crate::github::Client,
+    login: &str,
+) -> anyhow::Result<String> {
+    let payload = serde_json::json!({
+        "query": "query($login: String!) { \
+                    user(login: $login) { login } rateLimit { cost remaining resetAt } }",
+        "variables": { "login": login },
+    });
+
+    let resp = client
+        .graphql(&payload)
+        .await
+        .context("GraphQL user query failed")?;
+
+    let data = graphql_data(resp)?;
+    let login =
+        query_value!(data.user.login -> str).with_context(|| format!("user {login} not found"))?;
     Ok(login.to_string())
 }
@@ -1,20 +1,33 @@
+mod cache;
+mod capture;
 mod client;
 mod graphql;
+mod rate_limit;
+mod retry;
 
-pub use client::Client;
-pub use graphql::EventItem;
-#[cfg(test)]
-pub use graphql::EventKind;
+pub use cache::ResponseCache;
+pub(crate) use cache::host_dir_name;
+pub use capture::Capture;
+pub use client::{Client, ClientOptions};
+pub use graphql::{DiffStat, EventItem, EventKind, PlannedSearch, ReviewState};
 pub(crate) use graphql::{
-    query_closed_issues, query_closed_pull_requests, query_issue_comments, query_opened_issues,
-    query_opened_pull_requests, query_pull_request_review_contributions,
+    plan_closed_issues, plan_closed_pull_requests, plan_reopened, query_closed_issues,
+    query_closed_pull_requests, query_commits, query_discussion_answers, query_discussion_comments,
+    query_issue_comments, query_opened_discussions, query_opened_issues,
+    query_opened_pull_requests, query_pull_request_review_contributions, query_reopened,
+    query_team_members,
 };
+pub use rate_limit::RateLimitUsage;
+pub use retry::RetryPolicy;
 
 pub(crate) mod prelude {
-    pub use super::Client;
-    pub use super::EventItem;
+    pub use super::{Capture, Client, ClientOptions, RateLimitUsage, ResponseCache, RetryPolicy};
+    pub use super::{EventItem, EventKind, PlannedSearch};
     pub(crate) use super::{
-        query_closed_issues, query_closed_pull_requests, query_issue_comments, query_opened_issues,
-        query_opened_pull_requests, query_pull_request_review_contributions,
+        plan_closed_issues, plan_closed_pull_requests, plan_reopened, query_closed_issues,
+        query_closed_pull_requests, query_commits, query_discussion_answers,
+        query_discussion_comments, query_issue_comments, query_opened_discussions,
+        query_opened_issues, query_opened_pull_requests, query_pull_request_review_contributions,
+        query_reopened, query_team_members,
     };
 }
@@ -0,0 +1,147 @@
+use std::fmt;
+use std::sync::Mutex;
+
+use valq::query_value;
+
+/// GraphQL rate-limit points spent through one host's connection.
+///
+/// Shared by every client created for the host, so concurrent queries add up.
+#[derive(Debug, Default)]
+pub struct RateLimitUsage {
+    snapshot: Mutex<UsageSnapshot>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct UsageSnapshot {
+    pub requests: u64,
+    /// Responses served from the response cache, which cost nothing.
+    pub cached: u64,
+    /// Points spent, or `None` when the host does not report a rate limit (GHES with
+    /// rate limiting disabled).
+    pub cost: Option<u64>,
+    pub remaining: Option<u64>,
+    pub reset_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl RateLimitUsage {
+    /// Records one response and its `rateLimit { cost remaining resetAt }` object, if any.
+    pub(crate) fn record(&self, rate_limit: Option<&serde_json::Value>) {
+        let mut snapshot = self
+            .snapshot
+            .lock()
+            .expect("rate limit usage lock poisoned");
+        snapshot.requests += 1;
+
+        let Some(rate_limit) = rate_limit else {
+            return;
+        };
+        if let Some(cost) = query_value!(rate_limit.cost -> u64) {
+            snapshot.cost = Some(snapshot.cost.unwrap_or(0) + cost);
+        }
+        let Some(remaining) = query_value!(rate_limit.remaining -> u64) else {
+            return;
+        };
+        let reset_at = query_value!(rate_limit["resetAt"] -> str)
+            .and_then(|value| chrono::DateTime::parse_from_rfc3339(value).ok())
+            .map(|value| value.with_timezone(&chrono::Utc));
+
+        // Responses to concurrent queries arrive out of order; within a window the
+        // lowest remaining count is the latest one.
+        if reset_at > snapshot.reset_at || snapshot.remaining.is_none() {
+            snapshot.reset_at = reset_at;
+            snapshot.remaining = Some(remaining);
+        } else if reset_at == snapshot.reset_at {
+            snapshot.remaining = snapshot.remaining.min(Some(remaining));
+        }
+    }
+
+    pub(crate) fn record_cached(&self) {
+        let mut snapshot = self
+            .snapshot
+            .lock()
+            .expect("rate limit usage lock poisoned");
+        snapshot.cached += 1;
+    }
+
+    pub fn snapshot(&self) -> UsageSnapshot {
+        *self
+            .snapshot
+            .lock()
+            .expect("rate limit usage lock poisoned")
+    }
+}
+
+impl fmt::Display for UsageSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let requests = self.requests;
+        write!(
+            f,
+            "{requests} {}",
+            if requests == 1 { "request" } else { "requests" }
+        )?;
+        if self.cached > 0 {
+            write!(f, ", {} from cache", self.cached)?;
+        }
+        let Some(cost) = self.cost else {
+            return write!(f, ", rate limit not reported");
+        };
+        write!(f, ", {cost} points spent")?;
+        if let Some(remaining) = self.remaining {
+            write!(f, ", {remaining} remaining")?;
+        }
+        if let Some(reset_at) = self.reset_at {
+            write!(f, " until {}", reset_at.format("%Y-%m-%dT%H:%M:%SZ"))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate_limit(cost: u64, remaining: u64, reset_at: &str) -> serde_json::Value {
+        serde_json::json!({ "cost": cost, "remaining": remaining, "resetAt": reset_at })
+    }
+
+    #[test]
+    fn record_sums_cost_and_keeps_latest_remaining() {
+        let usage = RateLimitUsage::default();
+        usage.record(Some(&rate_limit(1, 4990, "2025-01-01T01:00:00Z")));
+        usage.record(Some(&rate_limit(2, 4995, "2025-01-01T01:00:00Z")));
+        usage.record(Some(&rate_limit(1, 4989, "2025-01-01T01:00:00Z")));
+        usage.record(None);
+
+        let snapshot = usage.snapshot();
+        assert_eq!(snapshot.requests, 4);
+        assert_eq!(snapshot.cost, Some(4));
+        assert_eq!(snapshot.remaining, Some(4989));
+        assert_eq!(
+            snapshot.to_string(),
+            "4 requests, 4 points spent, 4989 remaining until 2025-01-01T01:00:00Z"
+        );
+    }
+
+    #[test]
+    fn record_starts_over_when_the_window_resets() {
+        let usage = RateLimitUsage::default();
+        usage.record(Some(&rate_limit(1, 10, "2025-01-01T01:00:00Z")));
+        usage.record(Some(&rate_limit(1, 4999, "2025-01-01T02:00:00Z")));
+
+        let snapshot = usage.snapshot();
+        assert_eq!(snapshot.cost, Some(2));
+        assert_eq!(snapshot.remaining, Some(4999));
+    }
+
+    #[test]
+    fn snapshot_without_rate_limit_says_so() {
+        let usage = RateLimitUsage::default();
+        usage.record(None);
+        usage.record_cached();
+
+        assert_eq!(
+            usage.snapshot().to_string(),
+            "1 request, 1 from cache, rate limit not reported"
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use std::time::Duration;
+
+const BASE_DELAY: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+/// Rate-limit waits longer than this are reported instead of slept through.
+const MAX_SERVER_WAIT: Duration = Duration::from_secs(15 * 60);
+
+/// How many times a GraphQL request is attempted before its error is returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+}
+
+impl RetryPolicy {
+    pub const DEFAULT_ATTEMPTS: u32 = 4;
+
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+        }
+    }
+
+    /// Delay before retrying after failed attempt number `attempt` (1-based), or `None` to give up.
+    ///
+    /// A wait requested by the server is honoured as is; otherwise the delay backs off
+    /// exponentially. Both get jitter so that concurrent queries do not retry in lockstep.
+    pub(crate) fn delay(&self, attempt: u32, server_wait: Option<Duration>) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        match server_wait {
+            Some(wait) if wait > MAX_SERVER_WAIT => None,
+            Some(wait) => Some(wait + jitter(BASE_DELAY)),
+            None => {
+                let backoff = backoff(attempt);
+                Some(backoff / 2 + jitter(backoff / 2))
+            }
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_ATTEMPTS)
+    }
+}
+
+fn backoff(attempt: u32) -> Duration {
+    BASE_DELAY
+        .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+        .min(MAX_BACKOFF)
+}
+
+/// A random duration between zero and `max`.
+fn jitter(max: Duration) -> Duration {
+    use std::hash::{BuildHasher, Hasher};
+
+    let random = std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish();
+    max.mul_f64(random as f64 / u64::MAX as f64)
+}
+
+/// How long the server asked clients to wait, from the response headers.
+///
+/// `Retry-After` is used when present (secondary rate limits); otherwise, once the
+/// primary quota is exhausted, the wait lasts until `x-ratelimit-reset`.
+pub(crate) fn server_wait(
+    retry_after: Option<&str>,
+    remaining: Option<&str>,
+    reset: Option<&str>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Option<Duration> {
+    if let Some(seconds) = retry_after.and_then(|value| value.trim().parse::<u64>().ok()) {
+        return Some(Duration::from_secs(seconds));
+    }
+    if remaining.map(str::trim) != Some("0") {
+        return None;
+    }
+    let reset = reset?.trim().parse::<i64>().ok()?;
+    let seconds = reset.saturating_sub(now.timestamp()).max(0);
+    Some(Duration::from_secs(seconds as u64))
+}
+
+/// Whether a request that failed with HTTP `status` may succeed if sent again.
+pub(crate) fn is_retryable_status(status: u16, rate_limited: bool) -> bool {
+    match status {
+        429 => true,
+        403 => rate_limited,
+        status => (500..600).contains(&status),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn now() -> chrono::DateTime<chrono::Utc> {
+        chrono::Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn server_wait_prefers_retry_after() {
+        let reset = (now().timestamp() + 600).to_string();
+        assert_eq!(
+            server_wait(Some("30"), Some("0"), Some(&reset), now()),
+            Some(Duration::from_secs(30))
+        );
+    }
+
+    #[test]
+    fn server_wait_uses_reset_once_quota_is_exhausted() {
+        let reset = (now().timestamp() + 90).to_string();
+        assert_eq!(
+            server_wait(None, Some("0"), Some(&reset), now()),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(server_wait(None, Some("12"), Some(&reset), now()), None);
+        assert_eq!(server_wait(None, None, None, now()), None);
+    }
+
+    #[test]
+    fn delay_gives_up_after_max_attempts() {
+        let policy = RetryPolicy::new(3);
+        assert!(policy.delay(1, None).is_some());
+        assert!(policy.delay(2, None).is_some());
+        assert_eq!(policy.delay(3, None), None);
+        assert_eq!(RetryPolicy::new(1).delay(1, None), None);
+    }
+
+    #[test]
+    fn delay_backs_off_with_bounded_jitter() {
+        let policy = RetryPolicy::new(10);
+        for attempt in 1..10 {
+            let delay = policy.delay(attempt, None).unwrap();
+            let backoff = backoff(attempt);
+            assert!(
+                delay >= backoff / 2 && delay <= backoff,
+                "{attempt}: {delay:?}"
+            );
+        }
+        assert_eq!(backoff(9), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn delay_honours_server_wait_up_to_a_limit() {
+        let policy = RetryPolicy::default();
+        let delay = policy.delay(1, Some(Duration::from_secs(30))).unwrap();
+        assert!(delay >= Duration::from_secs(30) && delay <= Duration::from_secs(31));
+        assert_eq!(policy.delay(1, Some(Duration::from_secs(3600))), None);
+    }
+
+    #[test]
+    fn retryable_statuses() {
+        assert!(is_retryable_status(502, false));
+        assert!(is_retryable_status(429, false));
+        assert!(is_retryable_status(403, true));
+        assert!(!is_retryable_status(403, false));
+        assert!(!is_retryable_status(401, false));
+    }
+}
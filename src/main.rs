@@ -1,28 +1,122 @@
+mod archive;
+mod filter;
 mod formatter;
 mod github;
+mod period;
+mod range;
+mod xdg;
+use crate::archive::Archive;
+use crate::filter::{KindFilter, RepoFilter, parse_kind};
+use crate::formatter::Section;
 use crate::github::prelude::*;
+use crate::period::{Period, parse_period};
+use crate::range::{DateRange, Zone, parse_zone};
+use anyhow::Context;
 use clap::Parser;
-use futures::future::try_join_all;
+use futures::future::{BoxFuture, FutureExt, join_all, try_join_all};
+use futures::stream::{FuturesUnordered, TryStreamExt};
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
 
 #[derive(clap::Parser, Debug)]
-#[command(version, about = "GitHub activity digger")]
+#[command(
+    version,
+    about = "GitHub activity digger",
+    args_conflicts_with_subcommands = true
+)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    period: PeriodArgs,
+    #[command(flatten)]
+    people: PeopleArgs,
+    #[command(flatten)]
+    filter: FilterArgs,
+    #[command(flatten)]
+    output: OutputArgs,
+    #[command(flatten)]
+    fetch: FetchArgs,
+    #[arg(
+        long,
+        help = "Print the queries and search splits a report would run, without fetching events"
+    )]
+    dry_run: bool,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    #[command(about = "Fetch events and store them in the local archive")]
+    Sync {
+        #[command(flatten)]
+        period: PeriodArgs,
+        #[command(flatten)]
+        people: PeopleArgs,
+        #[command(flatten)]
+        filter: FilterArgs,
+        #[command(flatten)]
+        fetch: FetchArgs,
+        #[command(flatten)]
+        archive: ArchiveArgs,
+    },
+    #[command(about = "Render a report from the local archive without querying GitHub")]
+    Report {
+        #[command(flatten)]
+        period: PeriodArgs,
+        #[command(flatten)]
+        people: PeopleArgs,
+        #[command(flatten)]
+        filter: FilterArgs,
+        #[command(flatten)]
+        output: OutputArgs,
+        #[command(flatten)]
+        archive: ArchiveArgs,
+    },
+}
+
+/// Which days a report covers.
+#[derive(clap::Args, Debug)]
+struct PeriodArgs {
     #[arg(
         long,
         value_parser = parse_date,
         value_name = "YYYY-MM-DD",
-        help = "Start date",
-        default_value_t = today()
+        help = "Start date [default: today]"
     )]
-    from: chrono::NaiveDate,
+    from: Option<chrono::NaiveDate>,
     #[arg(
         long,
         value_parser = parse_date,
         value_name = "YYYY-MM-DD",
-        help = "End date",
-        default_value_t = today()
+        help = "End date [default: today]"
+    )]
+    to: Option<chrono::NaiveDate>,
+    #[arg(
+        long,
+        value_parser = parse_period,
+        value_name = "PERIOD",
+        conflicts_with_all = ["from", "to"],
+        help = "Period such as today, yesterday, this-week, last-week, this-month, last-month, \
+                2025-01, 2025-W03, 2025-Q2 or 7d"
     )]
-    to: chrono::NaiveDate,
+    period: Option<Period>,
+    #[arg(
+        long,
+        value_parser = parse_zone,
+        value_name = "ZONE",
+        default_value = "local",
+        help = "Time zone for date boundaries and displayed dates (IANA name or `local`)"
+    )]
+    tz: Zone,
+}
+
+/// Whose activity is reported, and on which hosts.
+#[derive(clap::Args, Debug)]
+struct PeopleArgs {
     #[arg(
         long,
         value_name = "HOST[,HOST...]",
@@ -33,84 +127,941 @@ struct Cli {
         env = "GH_HOST"
     )]
     hostname: Vec<String>,
+    #[arg(
+        long,
+        value_name = "LOGIN",
+        help = "Collect activity of another user instead of the authenticated one"
+    )]
+    user: Option<String>,
+    #[arg(
+        long,
+        value_name = "LOGIN[,LOGIN...]",
+        value_delimiter = ',',
+        value_parser = parse_login,
+        conflicts_with = "user",
+        help = "Collect a combined report for several users, grouped by person"
+    )]
+    users: Vec<String>,
+    #[arg(
+        long,
+        value_name = "ORG/TEAM",
+        value_parser = parse_team,
+        conflicts_with = "user",
+        help = "Collect a combined report for every member of a team, grouped by person"
+    )]
+    team: Option<Team>,
+}
+
+/// Which repositories and event kinds are reported.
+#[derive(clap::Args, Debug)]
+struct FilterArgs {
+    #[arg(
+        long,
+        value_name = "PATTERN[,PATTERN...]",
+        value_delimiter = ',',
+        value_parser = parse_repo_pattern,
+        help = "Only include repositories matching these globs (prefix with `!` to exclude)"
+    )]
+    repo: Vec<String>,
+    #[arg(
+        long,
+        value_name = "PATTERN[,PATTERN...]",
+        value_delimiter = ',',
+        value_parser = parse_excluded_repo_pattern,
+        help = "Exclude repositories matching these globs"
+    )]
+    exclude_repo: Vec<String>,
+    #[arg(
+        long,
+        value_name = "ORG[,ORG...]",
+        value_delimiter = ',',
+        value_parser = parse_login,
+        help = "Only include repositories owned by these organizations or users"
+    )]
+    org: Vec<String>,
+    #[arg(
+        long,
+        value_name = "KIND[,KIND...]",
+        value_delimiter = ',',
+        value_parser = parse_kind,
+        help = "Only include these event kinds (comment, review, opened, closed, merged or an exact kind name)"
+    )]
+    kind: Vec<String>,
+    #[arg(
+        long,
+        value_name = "KIND[,KIND...]",
+        value_delimiter = ',',
+        value_parser = parse_kind,
+        help = "Exclude these event kinds"
+    )]
+    exclude_kind: Vec<String>,
+}
+
+/// How a report is rendered.
+#[derive(clap::Args, Debug)]
+struct OutputArgs {
     #[arg(short, long, help = "Use compact list output")]
     compact: bool,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = OutputFormat::Markdown,
+        help = "Output format"
+    )]
+    format: OutputFormat,
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with = "format",
+        help = "Render the report with a MiniJinja template instead of a built-in format"
+    )]
+    template: Option<std::path::PathBuf>,
+}
+
+/// How events are fetched from GitHub.
+#[derive(clap::Args, Debug)]
+struct FetchArgs {
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..),
+        default_value_t = RetryPolicy::DEFAULT_ATTEMPTS,
+        help = "Attempts per GraphQL request before giving up on server errors and rate limits"
+    )]
+    max_attempts: u32,
+    #[arg(
+        long,
+        help = "Always query GitHub instead of reading or writing the response cache"
+    )]
+    no_cache: bool,
+    #[arg(
+        long,
+        conflicts_with = "no_cache",
+        help = "Ignore cached responses, but cache the fresh ones"
+    )]
+    refresh: bool,
+    #[arg(
+        long,
+        help = "Print GraphQL rate-limit usage per host on stderr when done"
+    )]
+    stats: bool,
+    #[arg(
+        long,
+        value_name = "DIR",
+        help = "Save every GraphQL request and response to DIR"
+    )]
+    record: Option<PathBuf>,
+    #[arg(
+        long,
+        value_name = "DIR",
+        conflicts_with = "record",
+        help = "Serve GraphQL responses saved with --record from DIR instead of GitHub"
+    )]
+    replay: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug)]
+struct ArchiveArgs {
+    #[arg(
+        long,
+        value_name = "DIR",
+        help = "Directory holding one SQLite archive per host [default: $XDG_DATA_HOME/gh-furik]"
+    )]
+    archive_dir: Option<PathBuf>,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputFormat {
+    Markdown,
+    Json,
+    Ndjson,
+    Csv,
+    Tsv,
+    Html,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Team {
+    org: String,
+    slug: String,
+}
+
+/// A host to collect from, and the GraphQL rate-limit points spent on it.
+struct Connection {
+    hostname: String,
+    options: ClientOptions,
+    usage: Arc<RateLimitUsage>,
+}
+
+impl Connection {
+    fn new(hostname: String, options: ClientOptions) -> Self {
+        Self {
+            hostname,
+            options,
+            usage: Arc::default(),
+        }
+    }
+
+    async fn client(&self) -> anyhow::Result<Client> {
+        Client::new(&self.hostname, &self.options, self.usage.clone()).await
+    }
+}
+
+/// How long cached responses are reused when the report ends before today.
+const CLOSED_PERIOD_CACHE_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+/// How long cached responses are reused when the report includes today.
+const OPEN_PERIOD_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Past days rarely change, so their responses are kept for long; a report that includes
+/// today should pick up new activity soon, but not re-query while iterating on its format.
+fn cache_ttl(to: chrono::NaiveDate, today: chrono::NaiveDate) -> Duration {
+    if to < today {
+        CLOSED_PERIOD_CACHE_TTL
+    } else {
+        OPEN_PERIOD_CACHE_TTL
+    }
+}
+
+/// Whose activity is collected on each host.
+struct People {
+    users: Vec<String>,
+    team: Option<Team>,
+}
+
+impl People {
+    /// Whether the report covers explicitly named people rather than the authenticated user.
+    fn is_named(&self) -> bool {
+        !self.users.is_empty() || self.team.is_some()
+    }
+
+    async fn clients(&self, connection: &Connection) -> anyhow::Result<Vec<Client>> {
+        let client = connection.client().await?;
+        if !self.is_named() {
+            return Ok(vec![client]);
+        }
+
+        let mut logins = self.users.clone();
+        if let Some(Team { org, slug }) = &self.team {
+            logins.extend(query_team_members(&client, org, slug).await?);
+        }
+        dedup_logins(&mut logins);
+
+        let client = &client;
+        let hostname = &connection.hostname;
+        try_join_all(logins.iter().map(|login| async move {
+            client
+                .for_user(login)
+                .await
+                .with_context(|| format!("failed to resolve user {login} on {hostname}"))
+        }))
+        .await
+    }
+
+    fn section_user(&self, client: &Client) -> Option<String> {
+        self.is_named().then(|| client.login().to_string())
+    }
+}
+
+/// Drops logins named more than once, ignoring case.
+fn dedup_logins(logins: &mut Vec<String>) {
+    let mut seen = HashSet::new();
+    logins.retain(|login| seen.insert(login.to_ascii_lowercase()));
+}
+
+/// Which events are collected for each person.
+struct Selection {
+    range: DateRange,
+    /// The date the report is run on, which searches up to today end at.
+    today: chrono::NaiveDate,
+    repos: RepoFilter,
+    kinds: KindFilter,
+    search_filter: String,
+}
+
+impl Selection {
+    fn new(
+        range: DateRange,
+        today: chrono::NaiveDate,
+        repos: RepoFilter,
+        kinds: KindFilter,
+    ) -> Self {
+        let search_filter = repos.search_qualifiers();
+        Self {
+            range,
+            today,
+            repos,
+            kinds,
+            search_filter,
+        }
+    }
+
+    /// Queries that can produce any of the selected kinds.
+    fn queries(&self) -> Vec<Query> {
+        Query::ALL
+            .into_iter()
+            .filter(|query| {
+                if query.is_opt_in() {
+                    self.kinds.names_any(query.kinds())
+                } else {
+                    self.kinds.wants_any(query.kinds())
+                }
+            })
+            .collect()
+    }
+
+    fn retain(&self, items: &mut Vec<EventItem>) {
+        items.retain(|item| self.kinds.matches(&item.kind) && self.repos.matches(&item.repository));
+    }
 }
 
 #[tokio::main]
-async fn main() -> anyhow::Result<()> {
+async fn main() -> anyhow::Result<ExitCode> {
     let Cli {
-        from,
-        to,
-        hostname,
-        compact,
+        command,
+        period,
+        people,
+        filter,
+        output,
+        fetch,
+        dry_run,
     } = Cli::parse();
+    match command {
+        None => report_from_github(period, people, filter, output, fetch, dry_run).await,
+        Some(Command::Sync {
+            period,
+            people,
+            filter,
+            fetch,
+            archive,
+        }) => sync(period, people, filter, fetch, archive).await,
+        Some(Command::Report {
+            period,
+            people,
+            filter,
+            output,
+            archive,
+        }) => report_from_archive(period, people, filter, output, archive),
+    }
+}
 
-    let results = try_join_all(
-        hostname
-            .into_iter()
-            .map(|host| fetch_events_for_host(host, from, to)),
+impl PeriodArgs {
+    fn range(&self, today: chrono::NaiveDate) -> DateRange {
+        let (from, to) = match self.period {
+            Some(period) => period.resolve(today),
+            None => (self.from.unwrap_or(today), self.to.unwrap_or(today)),
+        };
+        DateRange {
+            from,
+            to,
+            zone: self.tz,
+        }
+    }
+}
+
+impl PeopleArgs {
+    fn into_hosts_and_people(self) -> (Vec<String>, People) {
+        let people = People {
+            users: self.user.into_iter().chain(self.users).collect(),
+            team: self.team,
+        };
+        (self.hostname, people)
+    }
+}
+
+impl FilterArgs {
+    fn into_selection(self, range: DateRange, today: chrono::NaiveDate) -> Selection {
+        Selection::new(
+            range,
+            today,
+            RepoFilter::new(self.repo, self.exclude_repo, self.org),
+            KindFilter::new(&self.kind, &self.exclude_kind),
+        )
+    }
+}
+
+impl FetchArgs {
+    /// Resolves the report's date range and how to connect to GitHub for it.
+    ///
+    /// A replayed run covers the same days as when it was recorded.
+    fn prepare(
+        self,
+        period: &PeriodArgs,
+    ) -> anyhow::Result<(DateRange, chrono::NaiveDate, ClientOptions)> {
+        let capture = self
+            .record
+            .map(Capture::Record)
+            .or(self.replay.map(Capture::Replay));
+        let today = match &capture {
+            Some(capture) => capture.start(period.tz.today())?,
+            None => period.tz.today(),
+        };
+        let range = period.range(today);
+        // Recording must see every request, and replaying must not depend on the cache.
+        let cache = if self.no_cache || capture.is_some() {
+            None
+        } else {
+            ResponseCache::default_dir()
+                .map(|dir| ResponseCache::new(dir, cache_ttl(range.to, today), self.refresh))
+        };
+        if let Some(cache) = &cache
+            && let Err(error) = cache.prune(CLOSED_PERIOD_CACHE_TTL)
+        {
+            eprintln!("Warning: {error:#}");
+        }
+        let options = ClientOptions {
+            retry: RetryPolicy::new(self.max_attempts),
+            cache,
+            capture,
+        };
+        Ok((range, today, options))
+    }
+}
+
+impl OutputArgs {
+    fn render(&self, results: &[Section], zone: Zone) -> anyhow::Result<String> {
+        match &self.template {
+            Some(path) => crate::formatter::format_template(path, results, zone),
+            None => Ok(render(results, self.format, self.compact, zone)),
+        }
+    }
+}
+
+impl ArchiveArgs {
+    fn dir(self) -> anyhow::Result<PathBuf> {
+        self.archive_dir
+            .or_else(Archive::default_dir)
+            .context("cannot locate the archive directory; pass --archive-dir")
+    }
+}
+
+async fn report_from_github(
+    period: PeriodArgs,
+    people: PeopleArgs,
+    filter: FilterArgs,
+    output: OutputArgs,
+    fetch: FetchArgs,
+    dry_run: bool,
+) -> anyhow::Result<ExitCode> {
+    let stats = fetch.stats;
+    let (range, today, options) = fetch.prepare(&period)?;
+    let selection = filter.into_selection(range, today);
+    let (hostnames, people) = people.into_hosts_and_people();
+    let connections = connect(hostnames, &options);
+
+    let failures = if dry_run {
+        let outcomes = join_all(connections.iter().map(|connection| async {
+            let result = plan_for_host(connection, &people, &selection).await;
+            (connection.hostname.clone(), result)
+        }))
+        .await;
+        let (plans, failures) = partition_outcomes(outcomes)?;
+        print!("{}", plans.join("\n"));
+        failures
+    } else if output.format == OutputFormat::Ndjson {
+        let outcomes = join_all(connections.iter().map(|connection| async {
+            let result = stream_events_for_host(connection, &people, &selection).await;
+            (connection.hostname.clone(), result)
+        }))
+        .await;
+        // Events of a failed host may already be written; mark the host so that
+        // consumers can tell its events are incomplete.
+        for (host, result) in &outcomes {
+            if let Err(error) = result {
+                print!(
+                    "{}",
+                    crate::formatter::format_ndjson(&Section::failed(host.clone(), error))
+                );
+            }
+        }
+        let (_, failures) = partition_outcomes(outcomes)?;
+        failures
+    } else {
+        let outcomes = join_all(connections.iter().map(|connection| async {
+            let result = fetch_events_for_host(connection, &people, &selection).await;
+            (connection.hostname.clone(), result)
+        }))
+        .await;
+        let (per_host, failures) = partition_outcomes(outcomes)?;
+        print!(
+            "{}",
+            output.render(&with_failures(per_host, &failures), range.zone)?
+        );
+        failures
+    };
+
+    if stats {
+        print_stats(&connections);
+    }
+    Ok(exit_code(&failures))
+}
+
+/// Fetches the period's events and adds them to each host's archive.
+async fn sync(
+    period: PeriodArgs,
+    people: PeopleArgs,
+    filter: FilterArgs,
+    fetch: FetchArgs,
+    archive: ArchiveArgs,
+) -> anyhow::Result<ExitCode> {
+    let dir = archive.dir()?;
+    let stats = fetch.stats;
+    let (range, today, options) = fetch.prepare(&period)?;
+    let selection = filter.into_selection(range, today);
+    let (hostnames, people) = people.into_hosts_and_people();
+    let connections = connect(hostnames, &options);
+
+    let outcomes = join_all(connections.iter().map(|connection| async {
+        let result = sync_host(connection, &people, &selection, &dir).await;
+        (connection.hostname.clone(), result)
+    }))
+    .await;
+    let (summaries, failures) = partition_outcomes(outcomes)?;
+    for summary in summaries.concat() {
+        println!("{summary}");
+    }
+
+    if stats {
+        print_stats(&connections);
+    }
+    Ok(exit_code(&failures))
+}
+
+/// Renders the period's events from each host's archive without querying GitHub.
+fn report_from_archive(
+    period: PeriodArgs,
+    people: PeopleArgs,
+    filter: FilterArgs,
+    output: OutputArgs,
+    archive: ArchiveArgs,
+) -> anyhow::Result<ExitCode> {
+    let dir = archive.dir()?;
+    let today = period.tz.today();
+    let selection = filter.into_selection(period.range(today), today);
+    let (hostnames, people) = people.into_hosts_and_people();
+    if people.team.is_some() {
+        anyhow::bail!("--team needs GitHub to list the team's members; pass --users instead");
+    }
+
+    let outcomes = hostnames
+        .into_iter()
+        .map(|hostname| {
+            let result = archived_sections(&dir, &hostname, &people, &selection);
+            (hostname, result)
+        })
+        .collect();
+    let (per_host, failures) = partition_outcomes(outcomes)?;
+    print!(
+        "{}",
+        output.render(&with_failures(per_host, &failures), selection.range.zone)?
+    );
+    Ok(exit_code(&failures))
+}
+
+fn connect(hostnames: Vec<String>, options: &ClientOptions) -> Vec<Connection> {
+    hostnames
+        .into_iter()
+        .map(|hostname| Connection::new(hostname, options.clone()))
+        .collect()
+}
+
+fn print_stats(connections: &[Connection]) {
+    for connection in connections {
+        eprintln!("{}: {}", connection.hostname, connection.usage.snapshot());
+    }
+}
+
+/// Orders per-host sections by person and appends a section for each failed host.
+fn with_failures(per_host: Vec<Vec<Section>>, failures: &[HostFailure]) -> Vec<Section> {
+    let mut results = order_by_person(per_host);
+    results.extend(
+        failures
+            .iter()
+            .map(|(host, error)| Section::failed(host.clone(), error)),
+    );
+    results
+}
+
+/// A host whose activity could not be collected, with the reason.
+type HostFailure = (String, anyhow::Error);
+
+/// Exit status of a report where some hosts failed and the rest were rendered.
+const PARTIAL_FAILURE_EXIT_CODE: u8 = 2;
+
+/// Separates per-host results from failures, failing outright only when every host failed.
+fn partition_outcomes<T>(
+    outcomes: Vec<(String, anyhow::Result<T>)>,
+) -> anyhow::Result<(Vec<T>, Vec<HostFailure>)> {
+    let mut succeeded = Vec::new();
+    let mut failures = Vec::new();
+    for (host, result) in outcomes {
+        match result {
+            Ok(value) => succeeded.push(value),
+            Err(error) => failures.push((host, error)),
+        }
+    }
+
+    if succeeded.is_empty() && !failures.is_empty() {
+        let mut failures = failures.into_iter();
+        let (host, error) = failures.next().expect("failures is not empty");
+        for (host, error) in failures {
+            eprintln!("Error: failed to collect activity from {host}: {error:#}");
+        }
+        return Err(error.context(format!("failed to collect activity from {host}")));
+    }
+
+    for (host, error) in &failures {
+        eprintln!("Warning: failed to collect activity from {host}: {error:#}");
+    }
+    Ok((succeeded, failures))
+}
+
+fn exit_code(failures: &[HostFailure]) -> ExitCode {
+    if failures.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(PARTIAL_FAILURE_EXIT_CODE)
+    }
+}
+
+async fn fetch_events_for_host(
+    connection: &Connection,
+    people: &People,
+    selection: &Selection,
+) -> anyhow::Result<Vec<Section>> {
+    let clients = people.clients(connection).await?;
+    try_join_all(clients.iter().map(|client| async {
+        let items = fetch_all_events(client, selection).await?;
+        Ok(Section {
+            host: connection.hostname.clone(),
+            user: people.section_user(client),
+            items,
+            error: None,
+        })
+    }))
+    .await
+}
+
+/// Stores each person's events in the host's archive and describes what was added.
+async fn sync_host(
+    connection: &Connection,
+    people: &People,
+    selection: &Selection,
+    dir: &std::path::Path,
+) -> anyhow::Result<Vec<String>> {
+    let mut archive = Archive::open(&Archive::path(dir, &connection.hostname), true)?;
+    let clients = people.clients(connection).await?;
+    let events = try_join_all(
+        clients
+            .iter()
+            .map(|client| fetch_all_events(client, selection)),
     )
     .await?;
-    let output = format_host_outputs(&results, compact);
 
-    print!("{output}");
+    let mut summaries = Vec::new();
+    for (client, items) in clients.iter().zip(events) {
+        let added = archive.store(client.login(), &items)?;
+        summaries.push(format!(
+            "{} ({}): {} events, {added} new",
+            client.login(),
+            connection.hostname,
+            items.len()
+        ));
+    }
+    Ok(summaries)
+}
+
+/// Each person's archived events on `host`. Without named people, everyone in the archive
+/// is reported, under the host alone when the archive holds a single person.
+fn archived_sections(
+    dir: &std::path::Path,
+    host: &str,
+    people: &People,
+    selection: &Selection,
+) -> anyhow::Result<Vec<Section>> {
+    let archive = Archive::open(&Archive::path(dir, host), false)?;
+    let mut logins = if people.is_named() {
+        people.users.clone()
+    } else {
+        archive.logins()?
+    };
+    dedup_logins(&mut logins);
+    if !people.is_named() && logins.len() <= 1 {
+        let mut items = match logins.first() {
+            Some(login) => archive.events(login, selection.range)?,
+            None => Vec::new(),
+        };
+        selection.retain(&mut items);
+        return Ok(vec![Section {
+            host: host.to_string(),
+            user: None,
+            items,
+            error: None,
+        }]);
+    }
 
+    logins
+        .into_iter()
+        .map(|login| {
+            let mut items = archive.events(&login, selection.range)?;
+            selection.retain(&mut items);
+            Ok(Section {
+                host: host.to_string(),
+                user: Some(login),
+                items,
+                error: None,
+            })
+        })
+        .collect()
+}
+
+async fn stream_events_for_host(
+    connection: &Connection,
+    people: &People,
+    selection: &Selection,
+) -> anyhow::Result<()> {
+    let clients = people.clients(connection).await?;
+    try_join_all(clients.iter().map(|client| async {
+        let mut pending: FuturesUnordered<_> =
+            event_queries(client, selection).into_iter().collect();
+        let mut seen = HashSet::new();
+        while let Some(mut items) = pending.try_next().await? {
+            selection.retain(&mut items);
+            retain_unseen(&mut seen, &mut items);
+            if items.is_empty() {
+                continue;
+            }
+            let lines = crate::formatter::format_ndjson(&Section {
+                host: connection.hostname.clone(),
+                user: people.section_user(client),
+                items,
+                error: None,
+            });
+            let mut stdout = std::io::stdout().lock();
+            stdout.write_all(lines.as_bytes())?;
+            stdout.flush()?;
+        }
+        anyhow::Ok(())
+    }))
+    .await?;
     Ok(())
 }
 
-async fn fetch_events_for_host(
-    hostname: String,
-    from: chrono::NaiveDate,
-    to: chrono::NaiveDate,
-) -> anyhow::Result<(String, Vec<EventItem>)> {
-    let client = Client::new(&hostname).await?;
-    let items = fetch_all_events(&client, from, to).await?;
-    Ok((hostname, items))
+/// Flattens per-host sections so that each person's hosts are adjacent, keeping host order.
+fn order_by_person(per_host: Vec<Vec<Section>>) -> Vec<Section> {
+    let mut people: Vec<Option<String>> = Vec::new();
+    for section in per_host.iter().flatten() {
+        if !people.contains(&section.user) {
+            people.push(section.user.clone());
+        }
+    }
+
+    let mut sections: Vec<Section> = per_host.into_iter().flatten().collect();
+    sections.sort_by_key(|section| people.iter().position(|user| *user == section.user));
+    sections
 }
 
 async fn fetch_all_events(
     client: &Client,
-    from: chrono::NaiveDate,
-    to: chrono::NaiveDate,
+    selection: &Selection,
 ) -> anyhow::Result<Vec<EventItem>> {
-    let (
-        issue_comments,
-        review_contributions,
-        opened_issues,
-        opened_prs,
-        closed_issues,
-        closed_prs,
-    ) = tokio::try_join!(
-        query_issue_comments(client, from, to),
-        query_pull_request_review_contributions(client, from, to),
-        query_opened_issues(client, from, to),
-        query_opened_pull_requests(client, from, to),
-        query_closed_issues(client, from, to),
-        query_closed_pull_requests(client, from, to),
-    )?;
-
-    let items: Vec<_> = [
-        issue_comments,
-        review_contributions,
-        opened_issues,
-        opened_prs,
-        closed_issues,
-        closed_prs,
-    ]
-    .into_iter()
-    .flatten()
-    .collect();
-
+    let results = try_join_all(event_queries(client, selection)).await?;
+    let mut items = results.into_iter().flatten().collect();
+    selection.retain(&mut items);
+    retain_unseen(&mut HashSet::new(), &mut items);
     Ok(items)
 }
 
-fn parse_date(input: &str) -> anyhow::Result<chrono::NaiveDate, chrono::ParseError> {
-    chrono::NaiveDate::parse_from_str(input, "%Y-%m-%d")
+/// Kind, URL and time, which identify an event across queries.
+type EventKey = (&'static str, String, chrono::DateTime<chrono::Utc>);
+
+/// Drops events that are in `seen` or repeated within `items`, and adds the rest to `seen`.
+///
+/// Reopen events are found both by the closed searches and by the reopened search.
+fn retain_unseen(seen: &mut HashSet<EventKey>, items: &mut Vec<EventItem>) {
+    items.retain(|item| seen.insert((item.kind.name(), item.url.clone(), item.created_at)));
+}
+
+fn event_queries<'a>(
+    client: &'a Client,
+    selection: &'a Selection,
+) -> Vec<BoxFuture<'a, anyhow::Result<Vec<EventItem>>>> {
+    selection
+        .queries()
+        .into_iter()
+        .map(|query| query.run(client, selection))
+        .collect()
+}
+
+/// Describes the queries each person's report would run on a host, with the search
+/// windows and result counts, so the cost of a long report can be checked up front.
+async fn plan_for_host(
+    connection: &Connection,
+    people: &People,
+    selection: &Selection,
+) -> anyhow::Result<String> {
+    let clients = people.clients(connection).await?;
+    let plans = try_join_all(clients.iter().map(|client| async {
+        let section = Section {
+            host: connection.hostname.clone(),
+            user: people.section_user(client),
+            items: Vec::new(),
+            error: None,
+        };
+        let mut out = format!("{}\n", section.title());
+        for query in selection.queries() {
+            match query.plan(client, selection).await? {
+                None => out.push_str(&format!("- {}\n", query.name())),
+                Some(searches) => out.push_str(&format_search_plan(query, &searches)),
+            }
+        }
+        anyhow::Ok(out)
+    }))
+    .await?;
+    Ok(plans.join("\n"))
 }
 
-fn today() -> chrono::NaiveDate {
-    chrono::Utc::now().date_naive()
+fn format_search_plan(query: Query, searches: &[PlannedSearch]) -> String {
+    let results: u64 = searches.iter().map(|search| search.count).sum();
+    let pages: u64 = searches.iter().map(PlannedSearch::pages).sum();
+    let mut out = format!(
+        "- {}: {results} results in {} searches, {pages} pages\n",
+        query.name(),
+        searches.len()
+    );
+    for search in searches {
+        out.push_str(&format!("  - `{}`: {}\n", search.query, search.count));
+    }
+    out
+}
+
+/// A GraphQL query that collects one or more event kinds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Query {
+    IssueComments,
+    Reviews,
+    Commits,
+    OpenedIssues,
+    OpenedPullRequests,
+    OpenedDiscussions,
+    DiscussionComments,
+    DiscussionAnswers,
+    ClosedIssues,
+    ClosedPullRequests,
+    Reopened,
+}
+
+impl Query {
+    const ALL: [Query; 11] = [
+        Query::IssueComments,
+        Query::Reviews,
+        Query::Commits,
+        Query::OpenedIssues,
+        Query::OpenedPullRequests,
+        Query::OpenedDiscussions,
+        Query::DiscussionComments,
+        Query::DiscussionAnswers,
+        Query::ClosedIssues,
+        Query::ClosedPullRequests,
+        Query::Reopened,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Query::IssueComments => "issue comments",
+            Query::Reviews => "pull request reviews",
+            Query::Commits => "commits",
+            Query::OpenedIssues => "opened issues",
+            Query::OpenedPullRequests => "opened pull requests",
+            Query::OpenedDiscussions => "opened discussions",
+            Query::DiscussionComments => "discussion comments",
+            Query::DiscussionAnswers => "discussion answers",
+            Query::ClosedIssues => "closed issues",
+            Query::ClosedPullRequests => "closed pull requests",
+            Query::Reopened => "reopened issues and pull requests",
+        }
+    }
+
+    /// Whether the query only runs when `--kind` names its kinds, because its cost does not
+    /// depend on the period. The closed searches already report reopen events of items
+    /// closed in the period; finding the items that are still open means searching
+    /// everything updated since the period started. Answers are found by reading every
+    /// answer the user has written.
+    fn is_opt_in(self) -> bool {
+        matches!(self, Query::DiscussionAnswers | Query::Reopened)
+    }
+
+    fn kinds(self) -> &'static [EventKind] {
+        match self {
+            Query::IssueComments => &[EventKind::IssueComment],
+            Query::Reviews => &[
+                EventKind::PullRequestReview,
+                EventKind::PullRequestReviewComment,
+            ],
+            Query::Commits => &[EventKind::Commit],
+            Query::OpenedIssues => &[EventKind::IssueOpened],
+            Query::OpenedPullRequests => &[EventKind::PullRequestOpened],
+            Query::OpenedDiscussions => &[EventKind::DiscussionOpened],
+            Query::DiscussionComments => &[EventKind::DiscussionComment],
+            Query::DiscussionAnswers => &[EventKind::DiscussionAnswerMarked],
+            Query::ClosedIssues => &[EventKind::IssueClosed, EventKind::IssueReopened],
+            Query::ClosedPullRequests => &[
+                EventKind::PullRequestClosed,
+                EventKind::PullRequestMerged,
+                EventKind::PullRequestReopened,
+            ],
+            Query::Reopened => &[EventKind::IssueReopened, EventKind::PullRequestReopened],
+        }
+    }
+
+    fn run<'a>(
+        self,
+        client: &'a Client,
+        selection: &'a Selection,
+    ) -> BoxFuture<'a, anyhow::Result<Vec<EventItem>>> {
+        let range = selection.range;
+        let search_filter = selection.search_filter.as_str();
+        match self {
+            Query::IssueComments => query_issue_comments(client, range).boxed(),
+            Query::Reviews => query_pull_request_review_contributions(client, range).boxed(),
+            Query::Commits => query_commits(client, range, &selection.repos).boxed(),
+            Query::OpenedIssues => query_opened_issues(client, range).boxed(),
+            Query::OpenedPullRequests => query_opened_pull_requests(client, range).boxed(),
+            Query::OpenedDiscussions => query_opened_discussions(client, range).boxed(),
+            Query::DiscussionComments => query_discussion_comments(client, range).boxed(),
+            Query::DiscussionAnswers => query_discussion_answers(client, range).boxed(),
+            Query::ClosedIssues => query_closed_issues(client, range, search_filter).boxed(),
+            Query::ClosedPullRequests => {
+                query_closed_pull_requests(client, range, search_filter).boxed()
+            }
+            Query::Reopened => {
+                query_reopened(client, range, selection.today, search_filter).boxed()
+            }
+        }
+    }
+
+    /// Searches the query would run, or `None` for queries that page through a
+    /// connection instead of the search API.
+    async fn plan(
+        self,
+        client: &Client,
+        selection: &Selection,
+    ) -> anyhow::Result<Option<Vec<PlannedSearch>>> {
+        let range = selection.range;
+        let search_filter = selection.search_filter.as_str();
+        let searches = match self {
+            Query::ClosedIssues => plan_closed_issues(client, range, search_filter).await?,
+            Query::ClosedPullRequests => {
+                plan_closed_pull_requests(client, range, search_filter).await?
+            }
+            Query::Reopened => plan_reopened(client, range, selection.today, search_filter).await?,
+            _ => return Ok(None),
+        };
+        Ok(Some(searches))
+    }
+}
+
+fn parse_date(input: &str) -> anyhow::Result<chrono::NaiveDate, chrono::ParseError> {
+    chrono::NaiveDate::parse_from_str(input, "%Y-%m-%d")
 }
 
 fn parse_hostname(input: &str) -> Result<String, String> {
@@ -121,13 +1072,77 @@ fn parse_hostname(input: &str) -> Result<String, String> {
     Ok(host.to_string())
 }
 
-fn format_host_outputs(results: &[(String, Vec<EventItem>)], compact: bool) -> String {
+fn parse_login(input: &str) -> Result<String, String> {
+    let login = input.trim();
+    if login.is_empty() {
+        return Err("login must not be empty".to_string());
+    }
+    Ok(login.to_string())
+}
+
+fn parse_repo_pattern(input: &str) -> Result<String, String> {
+    let pattern = input.trim();
+    let repo = pattern.strip_prefix('!').unwrap_or(pattern);
+    if !repo.contains('/') {
+        return Err(format!(
+            "repository pattern `{input}` must be in OWNER/NAME form"
+        ));
+    }
+    Ok(pattern.to_string())
+}
+
+fn parse_excluded_repo_pattern(input: &str) -> Result<String, String> {
+    let pattern = parse_repo_pattern(input)?;
+    if pattern.starts_with('!') {
+        return Err(format!(
+            "`--exclude-repo` patterns are already exclusions; remove the `!` from `{input}`"
+        ));
+    }
+    Ok(pattern)
+}
+
+fn parse_team(input: &str) -> Result<Team, String> {
+    let (org, slug) = input
+        .trim()
+        .split_once('/')
+        .ok_or_else(|| "team must be in ORG/TEAM form".to_string())?;
+    if org.is_empty() || slug.is_empty() || slug.contains('/') {
+        return Err("team must be in ORG/TEAM form".to_string());
+    }
+    Ok(Team {
+        org: org.to_string(),
+        slug: slug.to_string(),
+    })
+}
+
+fn render(results: &[Section], format: OutputFormat, compact: bool, zone: Zone) -> String {
+    match format {
+        OutputFormat::Markdown => format_host_outputs(results, compact, zone),
+        OutputFormat::Json => crate::formatter::format_json(results),
+        OutputFormat::Ndjson => results
+            .iter()
+            .map(crate::formatter::format_ndjson)
+            .collect(),
+        OutputFormat::Csv => crate::formatter::format_csv(results, zone),
+        OutputFormat::Tsv => crate::formatter::format_tsv(results, zone),
+        OutputFormat::Html => crate::formatter::format_html(results, zone),
+    }
+}
+
+fn format_host_outputs(results: &[Section], compact: bool, zone: Zone) -> String {
     let sections: Vec<String> = results
         .iter()
-        .map(|(hostname, items)| {
-            crate::formatter::format_markdown(hostname, items, compact)
-                .trim_end_matches('\n')
-                .to_string()
+        .map(|section| {
+            let output = match &section.error {
+                Some(error) => crate::formatter::format_markdown_failure(&section.title(), error),
+                None => crate::formatter::format_markdown(
+                    &section.title(),
+                    &section.items,
+                    compact,
+                    zone,
+                ),
+            };
+            output.trim_end_matches('\n').to_string()
         })
         .collect();
     sections.join("\n\n")
@@ -137,6 +1152,49 @@ fn format_host_outputs(results: &[(String, Vec<EventItem>)], compact: bool) -> S
 mod tests {
     use super::*;
 
+    fn section(host: &str, user: Option<&str>) -> Section {
+        Section {
+            host: host.to_string(),
+            user: user.map(str::to_string),
+            items: vec![],
+            error: None,
+        }
+    }
+
+    #[test]
+    fn partition_outcomes_keeps_successful_hosts() {
+        let (succeeded, failures) = partition_outcomes(vec![
+            ("github.com".to_string(), Ok(1)),
+            (
+                "ghe.example.com".to_string(),
+                Err(anyhow::anyhow!("connection refused")),
+            ),
+        ])
+        .unwrap();
+
+        assert_eq!(succeeded, [1]);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, "ghe.example.com");
+        assert_eq!(
+            exit_code(&failures),
+            ExitCode::from(PARTIAL_FAILURE_EXIT_CODE)
+        );
+    }
+
+    #[test]
+    fn partition_outcomes_fails_when_every_host_failed() {
+        let error = partition_outcomes::<()>(vec![(
+            "ghe.example.com".to_string(),
+            Err(anyhow::anyhow!("connection refused")),
+        )])
+        .unwrap_err();
+
+        assert_eq!(
+            format!("{error:#}"),
+            "failed to collect activity from ghe.example.com: connection refused"
+        );
+    }
+
     #[test]
     fn parse_hostname_single() {
         let host = parse_hostname("github.com").unwrap();
@@ -155,14 +1213,88 @@ mod tests {
         assert!(error.contains("hostname must not be empty"));
     }
 
+    #[test]
+    fn parse_repo_pattern_requires_owner_and_name() {
+        assert_eq!(parse_repo_pattern(" myorg/* ").unwrap(), "myorg/*");
+        assert_eq!(
+            parse_repo_pattern("!myorg/sandbox-*").unwrap(),
+            "!myorg/sandbox-*"
+        );
+        assert!(
+            parse_repo_pattern("myorg")
+                .unwrap_err()
+                .contains("OWNER/NAME")
+        );
+    }
+
+    #[test]
+    fn parse_excluded_repo_pattern_rejects_negation() {
+        assert_eq!(
+            parse_excluded_repo_pattern(" myorg/legacy ").unwrap(),
+            "myorg/legacy"
+        );
+        assert!(
+            parse_excluded_repo_pattern("!myorg/legacy")
+                .unwrap_err()
+                .contains("remove the `!`")
+        );
+        assert!(Cli::try_parse_from(["gh-furik", "--exclude-repo", "!myorg/legacy"]).is_err());
+    }
+
+    #[test]
+    fn parse_team_splits_org_and_slug() {
+        let team = parse_team("octo-org/platform").unwrap();
+        assert_eq!(
+            team,
+            Team {
+                org: "octo-org".to_string(),
+                slug: "platform".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_team_rejects_malformed_input() {
+        for input in ["octo-org", "/platform", "octo-org/", "a/b/c"] {
+            let error = parse_team(input).unwrap_err();
+            assert!(error.contains("ORG/TEAM"), "{input}");
+        }
+    }
+
+    #[test]
+    fn order_by_person_groups_hosts_under_each_person() {
+        let sections = order_by_person(vec![
+            vec![
+                section("github.com", Some("alice")),
+                section("github.com", Some("bob")),
+            ],
+            vec![
+                section("ghe.example.com", Some("bob")),
+                section("ghe.example.com", Some("alice")),
+            ],
+        ]);
+
+        let titles: Vec<String> = sections.iter().map(Section::title).collect();
+        assert_eq!(
+            titles,
+            [
+                "alice (github.com)",
+                "alice (ghe.example.com)",
+                "bob (github.com)",
+                "bob (ghe.example.com)",
+            ]
+        );
+    }
+
     #[test]
     fn format_host_outputs_preserves_input_order() {
         let output = format_host_outputs(
             &[
-                ("github.com".to_string(), vec![]),
-                ("ghe.example.com".to_string(), vec![]),
+                section("github.com", None),
+                section("ghe.example.com", None),
             ],
             false,
+            Zone::UTC,
         );
 
         let github_index = output.find("# github.com").unwrap();
@@ -174,13 +1306,182 @@ mod tests {
     fn format_host_outputs_has_single_blank_line_between_hosts() {
         let output = format_host_outputs(
             &[
-                ("github.com".to_string(), vec![]),
-                ("ghe.example.com".to_string(), vec![]),
+                section("github.com", None),
+                section("ghe.example.com", None),
             ],
             false,
+            Zone::UTC,
         );
 
         assert!(output.contains("_No activity found._\n\n# ghe.example.com"));
         assert!(!output.contains("_No activity found._\n\n\n# ghe.example.com"));
     }
+
+    #[test]
+    fn render_json_includes_every_host() {
+        let output = render(
+            &[
+                section("github.com", None),
+                section("ghe.example.com", None),
+            ],
+            OutputFormat::Json,
+            false,
+            Zone::UTC,
+        );
+
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(value["hosts"][0]["host"], "github.com");
+        assert_eq!(value["hosts"][1]["host"], "ghe.example.com");
+    }
+
+    fn selection(kinds: &[&str]) -> Selection {
+        let kinds: Vec<String> = kinds.iter().map(|kind| kind.to_string()).collect();
+        Selection::new(
+            DateRange {
+                from: chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+                to: chrono::NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+                zone: Zone::UTC,
+            },
+            chrono::NaiveDate::from_ymd_opt(2025, 3, 31).unwrap(),
+            RepoFilter::new(vec![], vec![], vec![]),
+            KindFilter::new(&kinds, &[]),
+        )
+    }
+
+    #[test]
+    fn selection_queries_skip_queries_without_selected_kinds() {
+        assert_eq!(
+            selection(&[]).queries(),
+            [&Query::ALL[..7], &Query::ALL[8..10]].concat()
+        );
+        assert_eq!(selection(&["answer"]).queries(), [Query::DiscussionAnswers]);
+        assert_eq!(selection(&["review"]).queries(), [Query::Reviews]);
+        assert_eq!(
+            selection(&["merged", "reopened"]).queries(),
+            [
+                Query::ClosedIssues,
+                Query::ClosedPullRequests,
+                Query::Reopened
+            ]
+        );
+        assert_eq!(
+            selection(&["issue_reopened"]).queries(),
+            [Query::ClosedIssues, Query::Reopened]
+        );
+    }
+
+    #[test]
+    fn retain_unseen_drops_events_found_by_another_query() {
+        let reopened = |day: u32| EventItem {
+            kind: EventKind::IssueReopened,
+            created_at: chrono::DateTime::parse_from_rfc3339(&format!(
+                "2025-01-{day:02}T00:00:00Z"
+            ))
+            .unwrap()
+            .to_utc(),
+            url: "https://example.test/issues/1".to_string(),
+            body: None,
+            repository: "o/r".to_string(),
+            subject_title: "Bug".to_string(),
+            subject_url: "https://example.test/issues/1".to_string(),
+            diff_stat: None,
+            review_state: None,
+        };
+        let closed = EventItem {
+            kind: EventKind::IssueClosed,
+            ..reopened(10)
+        };
+        let mut seen = HashSet::new();
+        let mut from_closed = vec![closed.clone(), reopened(10)];
+        retain_unseen(&mut seen, &mut from_closed);
+        assert_eq!(from_closed, [closed, reopened(10)]);
+
+        let mut from_reopened = vec![reopened(10), reopened(20)];
+        retain_unseen(&mut seen, &mut from_reopened);
+        assert_eq!(from_reopened, [reopened(20)]);
+    }
+
+    #[test]
+    fn format_search_plan_lists_windows_with_counts_and_pages() {
+        let output = format_search_plan(
+            Query::ClosedIssues,
+            &[
+                PlannedSearch {
+                    query: "is:issue involves:me closed:a..b".to_string(),
+                    count: 980,
+                },
+                PlannedSearch {
+                    query: "is:issue involves:me closed:c..d".to_string(),
+                    count: 20,
+                },
+            ],
+        );
+
+        assert_eq!(
+            output,
+            "- closed issues: 1000 results in 2 searches, 21 pages\n\
+             \x20 - `is:issue involves:me closed:a..b`: 980\n\
+             \x20 - `is:issue involves:me closed:c..d`: 20\n"
+        );
+    }
+
+    #[test]
+    fn cache_ttl_is_short_for_reports_that_include_today() {
+        let today = chrono::NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        assert_eq!(
+            cache_ttl(today.pred_opt().unwrap(), today),
+            CLOSED_PERIOD_CACHE_TTL
+        );
+        assert_eq!(cache_ttl(today, today), OPEN_PERIOD_CACHE_TTL);
+    }
+
+    #[test]
+    fn archived_sections_group_by_person_only_when_needed() {
+        let dir =
+            std::env::temp_dir().join(format!("gh-furik-archive-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let comment = |login: &str| EventItem {
+            kind: EventKind::IssueComment,
+            created_at: chrono::DateTime::parse_from_rfc3339("2025-01-10T00:00:00Z")
+                .unwrap()
+                .to_utc(),
+            url: format!("https://example.test/issues/1#{login}"),
+            body: None,
+            repository: "o/r".to_string(),
+            subject_title: "Bug".to_string(),
+            subject_url: "https://example.test/issues/1".to_string(),
+            diff_stat: None,
+            review_state: None,
+        };
+        let mut archive = Archive::open(&Archive::path(&dir, "github.com"), true).unwrap();
+        archive.store("alice", &[comment("alice")]).unwrap();
+        let people = |users: &[&str]| People {
+            users: users.iter().map(|user| user.to_string()).collect(),
+            team: None,
+        };
+        let users = |sections: Vec<Section>| -> Vec<Option<String>> {
+            sections.into_iter().map(|section| section.user).collect()
+        };
+
+        let only_alice =
+            archived_sections(&dir, "github.com", &people(&[]), &selection(&[])).unwrap();
+        assert_eq!(only_alice.len(), 1);
+        assert_eq!(only_alice[0].user, None);
+        assert_eq!(only_alice[0].items.len(), 1);
+
+        archive.store("bob", &[comment("bob")]).unwrap();
+        let everyone =
+            archived_sections(&dir, "github.com", &people(&[]), &selection(&[])).unwrap();
+        assert_eq!(
+            users(everyone),
+            [Some("alice".to_string()), Some("bob".to_string())]
+        );
+        let named =
+            archived_sections(&dir, "github.com", &people(&["bob"]), &selection(&[])).unwrap();
+        assert_eq!(users(named), [Some("bob".to_string())]);
+        assert!(archived_sections(&dir, "ghe.example.com", &people(&[]), &selection(&[])).is_err());
+
+        drop(archive);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
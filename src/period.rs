@@ -0,0 +1,224 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+/// Longest `Nd` period accepted, about a century; longer ones would overflow dates.
+const MAX_DAYS: u32 = 36500;
+
+/// A named or calendar period accepted by `--period`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    Today,
+    Yesterday,
+    ThisWeek,
+    LastWeek,
+    ThisMonth,
+    LastMonth,
+    Month { year: i32, month: u32 },
+    IsoWeek { year: i32, week: u32 },
+    Quarter { year: i32, quarter: u32 },
+    Days(u32),
+}
+
+impl Period {
+    /// Resolves the period to an inclusive `(from, to)` date pair relative to `today`.
+    ///
+    /// Weeks start on Monday. Periods that contain `today` end on `today`.
+    pub fn resolve(self, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        match self {
+            Period::Today => (today, today),
+            Period::Yesterday => {
+                let yesterday = today - Duration::days(1);
+                (yesterday, yesterday)
+            }
+            Period::ThisWeek => (week_start(today), today),
+            Period::LastWeek => {
+                let start = week_start(today) - Duration::weeks(1);
+                (start, start + Duration::days(6))
+            }
+            Period::ThisMonth => (month_start(today.year(), today.month()), today),
+            Period::LastMonth => {
+                let this_month = month_start(today.year(), today.month());
+                let last_day = this_month - Duration::days(1);
+                (month_start(last_day.year(), last_day.month()), last_day)
+            }
+            Period::Month { year, month } => (month_start(year, month), month_end(year, month)),
+            Period::IsoWeek { year, week } => {
+                let start = NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)
+                    .expect("ISO week is validated by parse_period");
+                (start, start + Duration::days(6))
+            }
+            Period::Quarter { year, quarter } => {
+                let first_month = (quarter - 1) * 3 + 1;
+                (
+                    month_start(year, first_month),
+                    month_end(year, first_month + 2),
+                )
+            }
+            Period::Days(days) => (today - Duration::days(i64::from(days) - 1), today),
+        }
+    }
+}
+
+pub fn parse_period(input: &str) -> Result<Period, String> {
+    let value = input.trim().to_ascii_lowercase();
+    let period = match value.as_str() {
+        "today" => Period::Today,
+        "yesterday" => Period::Yesterday,
+        "this-week" => Period::ThisWeek,
+        "last-week" => Period::LastWeek,
+        "this-month" => Period::ThisMonth,
+        "last-month" => Period::LastMonth,
+        _ => parse_calendar_period(&value).ok_or_else(|| {
+            format!(
+                "invalid period `{input}`; expected today, yesterday, this-week, last-week, \
+                 this-month, last-month, YYYY-MM, YYYY-Www, YYYY-Qn or Nd (N up to {MAX_DAYS})"
+            )
+        })?,
+    };
+    Ok(period)
+}
+
+fn parse_calendar_period(value: &str) -> Option<Period> {
+    if let Some(days) = value.strip_suffix('d') {
+        let days: u32 = days.parse().ok()?;
+        return (1..=MAX_DAYS).contains(&days).then_some(Period::Days(days));
+    }
+
+    let (year, rest) = value.split_once('-')?;
+    if year.len() != 4 {
+        return None;
+    }
+    let year: i32 = year.parse().ok()?;
+
+    if let Some(week) = rest.strip_prefix('w') {
+        let week: u32 = week.parse().ok()?;
+        NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)?;
+        return Some(Period::IsoWeek { year, week });
+    }
+    if let Some(quarter) = rest.strip_prefix('q') {
+        let quarter: u32 = quarter.parse().ok()?;
+        return (1..=4)
+            .contains(&quarter)
+            .then_some(Period::Quarter { year, quarter });
+    }
+
+    let month: u32 = rest.parse().ok()?;
+    NaiveDate::from_ymd_opt(year, month, 1)?;
+    Some(Period::Month { year, month })
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(i64::from(date.weekday().num_days_from_monday()))
+}
+
+fn month_start(year: i32, month: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, 1).expect("month is validated by parse_period")
+}
+
+fn month_end(year: i32, month: u32) -> NaiveDate {
+    let (next_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    month_start(next_year, next_month) - Duration::days(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn resolve(input: &str, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        parse_period(input).unwrap().resolve(today)
+    }
+
+    #[test]
+    fn relative_days() {
+        let today = date(2025, 3, 1);
+        assert_eq!(resolve("today", today), (today, today));
+        assert_eq!(
+            resolve("yesterday", today),
+            (date(2025, 2, 28), date(2025, 2, 28))
+        );
+        assert_eq!(resolve("7d", today), (date(2025, 2, 23), today));
+        assert_eq!(resolve("1d", today), (today, today));
+    }
+
+    #[test]
+    fn weeks_start_on_monday() {
+        // 2025-01-15 is a Wednesday.
+        let today = date(2025, 1, 15);
+        assert_eq!(resolve("this-week", today), (date(2025, 1, 13), today));
+        assert_eq!(
+            resolve("last-week", today),
+            (date(2025, 1, 6), date(2025, 1, 12))
+        );
+    }
+
+    #[test]
+    fn last_month_crosses_year_boundary() {
+        assert_eq!(
+            resolve("last-month", date(2025, 1, 10)),
+            (date(2024, 12, 1), date(2024, 12, 31))
+        );
+        assert_eq!(
+            resolve("this-month", date(2025, 1, 10)),
+            (date(2025, 1, 1), date(2025, 1, 10))
+        );
+    }
+
+    #[test]
+    fn calendar_periods() {
+        let today = date(2025, 6, 1);
+        assert_eq!(
+            resolve("2024-02", today),
+            (date(2024, 2, 1), date(2024, 2, 29))
+        );
+        assert_eq!(
+            resolve("2025-W03", today),
+            (date(2025, 1, 13), date(2025, 1, 19))
+        );
+        assert_eq!(
+            resolve("2025-W01", today),
+            (date(2024, 12, 30), date(2025, 1, 5))
+        );
+        assert_eq!(
+            resolve("2025-Q2", today),
+            (date(2025, 4, 1), date(2025, 6, 30))
+        );
+        assert_eq!(
+            resolve("2025-q4", today),
+            (date(2025, 10, 1), date(2025, 12, 31))
+        );
+    }
+
+    #[test]
+    fn resolves_the_longest_day_period() {
+        let today = date(2025, 3, 10);
+        assert_eq!(
+            resolve(&format!("{MAX_DAYS}d"), today),
+            (today - Duration::days(i64::from(MAX_DAYS) - 1), today)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_periods() {
+        for input in [
+            "",
+            "0d",
+            "36501d",
+            "4000000000d",
+            "2025-13",
+            "2025-W54",
+            "2025-Q5",
+            "25-01",
+            "next-week",
+        ] {
+            let error = parse_period(input).unwrap_err();
+            assert!(error.contains("invalid period"), "{input}: {error}");
+        }
+    }
+}
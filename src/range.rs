@@ -0,0 +1,141 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+/// Time zone used to interpret `--from`/`--to` and to display dates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Zone {
+    Local,
+    Named(chrono_tz::Tz),
+}
+
+impl Zone {
+    #[cfg(test)]
+    pub const UTC: Zone = Zone::Named(chrono_tz::UTC);
+
+    pub fn today(self) -> NaiveDate {
+        self.date_of(Utc::now())
+    }
+
+    /// Calendar date of `dt` in this zone.
+    pub fn date_of(self, dt: DateTime<Utc>) -> NaiveDate {
+        match self {
+            Zone::Local => dt.with_timezone(&chrono::Local).date_naive(),
+            Zone::Named(tz) => dt.with_timezone(&tz).date_naive(),
+        }
+    }
+
+    /// `dt` as an RFC 3339 timestamp with this zone's offset.
+    pub fn to_rfc3339(self, dt: DateTime<Utc>) -> String {
+        match self {
+            Zone::Local => dt.with_timezone(&chrono::Local).to_rfc3339(),
+            Zone::Named(tz) => dt.with_timezone(&tz).to_rfc3339(),
+        }
+    }
+
+    /// First instant of `date` in this zone.
+    pub fn start_of_day(self, date: NaiveDate) -> DateTime<Utc> {
+        let midnight = date.and_hms_opt(0, 0, 0).expect("midnight is a valid time");
+        match self {
+            Zone::Local => local_to_utc(&chrono::Local, midnight),
+            Zone::Named(tz) => local_to_utc(&tz, midnight),
+        }
+    }
+}
+
+/// Converts a local wall-clock time to UTC, moving forward past DST gaps.
+fn local_to_utc<T: TimeZone>(tz: &T, local: NaiveDateTime) -> DateTime<Utc> {
+    let mut candidate = local;
+    for _ in 0..4 {
+        if let Some(dt) = tz.from_local_datetime(&candidate).earliest() {
+            return dt.with_timezone(&Utc);
+        }
+        candidate += chrono::Duration::minutes(30);
+    }
+    Utc.from_utc_datetime(&local)
+}
+
+pub fn parse_zone(input: &str) -> Result<Zone, String> {
+    let value = input.trim();
+    if value.eq_ignore_ascii_case("local") {
+        return Ok(Zone::Local);
+    }
+    value
+        .parse::<chrono_tz::Tz>()
+        .map(Zone::Named)
+        .map_err(|_| format!("unknown time zone `{input}`; expected `local` or an IANA name"))
+}
+
+/// Inclusive range of calendar days interpreted in `zone`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateRange {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub zone: Zone,
+}
+
+impl DateRange {
+    /// First instant of `from`.
+    pub fn start(&self) -> DateTime<Utc> {
+        self.zone.start_of_day(self.from)
+    }
+
+    /// Last second of `to`.
+    pub fn end(&self) -> DateTime<Utc> {
+        let next = self.to.succ_opt().unwrap_or(self.to);
+        self.zone.start_of_day(next) - chrono::Duration::seconds(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn tokyo() -> Zone {
+        parse_zone("Asia/Tokyo").unwrap()
+    }
+
+    #[test]
+    fn parse_zone_accepts_local_and_iana_names() {
+        assert_eq!(parse_zone("local").unwrap(), Zone::Local);
+        assert_eq!(parse_zone("UTC").unwrap(), Zone::UTC);
+        assert_eq!(tokyo(), Zone::Named(chrono_tz::Asia::Tokyo));
+        assert!(
+            parse_zone("Mars/Olympus")
+                .unwrap_err()
+                .contains("unknown time zone")
+        );
+    }
+
+    #[test]
+    fn range_boundaries_follow_zone() {
+        let range = DateRange {
+            from: date(2025, 1, 1),
+            to: date(2025, 1, 31),
+            zone: tokyo(),
+        };
+
+        assert_eq!(range.start().to_rfc3339(), "2024-12-31T15:00:00+00:00");
+        assert_eq!(range.end().to_rfc3339(), "2025-01-31T14:59:59+00:00");
+    }
+
+    #[test]
+    fn date_of_uses_zone_calendar_day() {
+        // 08:30 in Tokyo on 2025-01-02 is still 2025-01-01 in UTC.
+        let morning = Utc.with_ymd_and_hms(2025, 1, 1, 23, 30, 0).unwrap();
+        assert_eq!(tokyo().date_of(morning), date(2025, 1, 2));
+        assert_eq!(Zone::UTC.date_of(morning), date(2025, 1, 1));
+    }
+
+    #[test]
+    fn start_of_day_skips_dst_gap() {
+        // Clocks in Santiago jumped from 00:00 to 01:00 on 2024-09-08.
+        let zone = parse_zone("America/Santiago").unwrap();
+        assert_eq!(
+            zone.start_of_day(date(2024, 9, 8)).to_rfc3339(),
+            "2024-09-08T04:00:00+00:00"
+        );
+    }
+}
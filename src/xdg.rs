@@ -0,0 +1,52 @@
+use std::path::{Path, PathBuf};
+
+/// `$<xdg_var>/gh-furik`, falling back to `~/<home_dir>/gh-furik` and then to
+/// `%LOCALAPPDATA%\gh-furik`.
+///
+/// Empty variables count as unset, as the XDG specification requires.
+pub(crate) fn app_dir(xdg_var: &str, home_dir: &str) -> Option<PathBuf> {
+    let base = non_empty_env(xdg_var)
+        .map(PathBuf::from)
+        .or_else(|| non_empty_env("HOME").map(|home| Path::new(&home).join(home_dir)))
+        .or_else(|| non_empty_env("LOCALAPPDATA").map(PathBuf::from))?;
+    Some(base.join("gh-furik"))
+}
+
+fn non_empty_env(key: &str) -> Option<String> {
+    std::env::var(key).ok().filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use temp_env::with_vars;
+
+    #[test]
+    fn app_dir_prefers_the_xdg_variable() {
+        let vars = |xdg, home| {
+            [
+                ("XDG_DATA_HOME", xdg),
+                ("HOME", home),
+                ("LOCALAPPDATA", Some("C:\\Users\\me\\AppData\\Local")),
+            ]
+        };
+        with_vars(vars(Some("/xdg"), Some("/home/me")), || {
+            assert_eq!(
+                app_dir("XDG_DATA_HOME", ".local/share"),
+                Some(PathBuf::from("/xdg/gh-furik"))
+            )
+        });
+        with_vars(vars(Some(""), Some("/home/me")), || {
+            assert_eq!(
+                app_dir("XDG_DATA_HOME", ".local/share"),
+                Some(PathBuf::from("/home/me/.local/share/gh-furik"))
+            )
+        });
+        with_vars(vars(None, None), || {
+            assert_eq!(
+                app_dir("XDG_DATA_HOME", ".local/share"),
+                Some(Path::new("C:\\Users\\me\\AppData\\Local").join("gh-furik"))
+            )
+        });
+    }
+}